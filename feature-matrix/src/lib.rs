@@ -0,0 +1,157 @@
+//! Uses `new` under every feature combination, checked by `tests/matrix.rs`.
+//!
+//! The dependency is renamed to `constructors` to check that nothing assumes
+//! the crate is reachable as `::new`.
+
+#![no_std]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
+use core::{cell::Cell, mem::MaybeUninit};
+
+use constructors::{const_new, emplace, init, new, try_from, try_new, with};
+
+/// A value with one constructor of each kind.
+#[derive(Debug, Default)]
+pub struct Point {
+	/// The horizontal coordinate.
+	pub x: i32,
+	/// The vertical coordinate.
+	pub y: i32,
+}
+
+impl Point {
+	/// Creates a point.
+	#[must_use]
+	pub const fn new(x: i32, y: i32) -> Self {
+		Self { x, y }
+	}
+
+	/// Creates a point with non-negative coordinates.
+	///
+	/// # Errors
+	///
+	/// Returns the point unchanged if either coordinate is negative.
+	pub const fn try_new(x: i32, y: i32) -> Result<Self, (i32, i32)> {
+		if x < 0 || y < 0 {
+			Err((x, y))
+		} else {
+			Ok(Self { x, y })
+		}
+	}
+
+	/// Moves an existing point, for reuse through a pool.
+	pub const fn reinit(&mut self, x: i32, y: i32) {
+		self.x = x;
+		self.y = y;
+	}
+
+	/// Creates a point on the diagonal.
+	#[must_use]
+	pub const fn with_diagonal(xy: i32) -> Self {
+		Self { x: xy, y: xy }
+	}
+}
+
+/// The origin, built in a const context.
+pub const ORIGIN: Point = const_new!(Point(0, 0));
+
+/// Uses the forms available without `alloc`.
+pub fn core_forms() {
+	let _ = new!(Point(1, 2));
+	let _: Result<Point, _> = try_new!(Point(1, 2));
+	let _ = with!(Point: diagonal(3));
+	let _: Cell<Point> = new!(Cell<_> <- Point(1, 2));
+	let _: Result<u8, _> = try_from!(u8(300u16));
+
+	let mut slot = MaybeUninit::uninit();
+	let point: &mut Point = emplace!(&mut slot => Point { x: 1, y: 2 });
+	point.x += 1;
+	let _ = init!(Point { x: 1, y: 2 });
+}
+
+/// Uses the forms that need `alloc`.
+#[cfg(feature = "alloc")]
+pub fn alloc_forms() {
+	use alloc::{boxed::Box, rc::Rc, vec::Vec};
+	use constructors::{boxed, pin, rc, Pool};
+
+	let _: Box<Point> = boxed!(Point(1, 2));
+	let _: Rc<Point> = rc!(Point(1, 2));
+	let _ = pin!(Point(1, 2));
+
+	let mut points = Vec::new();
+	emplace!(&mut points => Point { x: 1, y: 2 });
+
+	let pool = Pool::<Point>::new();
+	let _ = new!(in &pool => Point(1, 2));
+}
+
+/// Uses the forms that need `std`.
+#[cfg(feature = "std")]
+pub fn std_forms() {
+	use std::sync::{Arc, Mutex};
+
+	let _: Arc<Mutex<Point>> = new!(Arc<_> <- Mutex<_> <- Point(1, 2));
+}
+
+/// Uses the derives.
+#[cfg(feature = "derive")]
+pub mod derived {
+	/// A derived constructor.
+	#[derive(Debug, constructors::New, constructors::With)]
+	#[new(crate = ::constructors)]
+	pub struct Config {
+		/// The name.
+		pub name: &'static str,
+		/// An optional limit.
+		pub limit: Option<u32>,
+	}
+
+	/// A derived parser.
+	#[derive(Debug, constructors::FromStr)]
+	#[new(crate = ::constructors)]
+	pub enum Mode {
+		/// Fast.
+		Fast,
+		/// Slow.
+		Slow,
+	}
+
+	#[cfg(feature = "alloc")]
+	#[allow(clippy::trivially_copy_pass_by_ref)] // validators take their field by reference
+	const fn positive(value: &u32) -> Result<(), &'static str> {
+		if *value == 0 {
+			Err("zero")
+		} else {
+			Ok(())
+		}
+	}
+
+	/// A derived builder.
+	#[cfg(feature = "alloc")]
+	#[derive(Debug, constructors::Builder, constructors::TryNew)]
+	#[new(crate = ::constructors)]
+	pub struct Limits {
+		/// The lower bound.
+		#[new(validate = positive)]
+		pub low: u32,
+		/// The upper bound.
+		#[new(default)]
+		pub high: u32,
+	}
+}
+
+/// Uses `new_in!` with an `allocator-api2` allocator.
+#[cfg(feature = "allocator-api2")]
+pub fn allocator_forms() {
+	use constructors::{
+		allocator_api2::{alloc::Global, boxed::Box},
+		new_in,
+	};
+
+	let _: Box<u32, Global> = new_in!(Box<_>(1u32) in Global);
+}
@@ -0,0 +1,31 @@
+//! Checks `feature-matrix` under every combination of `new`'s features.
+
+use std::{env, path::Path, process::Command};
+
+const FEATURES: &[&str] = &["alloc", "std", "derive", "allocator-api2"];
+
+#[test]
+fn every_feature_combination_builds() {
+	let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+	let target = root.join("target").join("feature-matrix");
+
+	for mask in 0..1_u32 << FEATURES.len() {
+		let features = FEATURES
+			.iter()
+			.enumerate()
+			.filter(|(bit, _)| mask & 1 << bit != 0)
+			.map(|(_, feature)| *feature)
+			.collect::<Vec<_>>()
+			.join(",");
+
+		let status = Command::new(env!("CARGO"))
+			.current_dir(&root)
+			.env("CARGO_TARGET_DIR", &target)
+			.args(["check", "--quiet", "--offline", "-p", "feature-matrix"])
+			.args(["--no-default-features", "--features", &features])
+			.status()
+			.expect("cargo should run");
+
+		assert!(status.success(), "features [{features}] failed to build");
+	}
+}
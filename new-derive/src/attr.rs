@@ -0,0 +1,238 @@
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::{
+	ext::IdentExt, parse_quote, Attribute, Data, DeriveInput, Error, Expr, Field, GenericArgument,
+	Ident, Member, Path, PathArguments, Result, Token, Type, TypePath,
+};
+
+/// Struct-level `#[new(...)]` options.
+#[derive(Default)]
+pub struct Options {
+	name: Option<Ident>,
+	krate: Option<Path>,
+}
+
+impl Options {
+	pub fn parse(attrs: &[Attribute]) -> Result<Self> {
+		let mut options = Self::default();
+
+		for attr in attrs.iter().filter(|attr| attr.path().is_ident("new")) {
+			attr.parse_nested_meta(|meta| {
+				if meta.path.is_ident("name") {
+					options.name = Some(meta.value()?.parse()?);
+				} else if meta.path.is_ident("crate") {
+					options.krate = Some(meta.value()?.parse()?);
+				} else {
+					return Err(meta.error("unknown `new` attribute"));
+				}
+
+				Ok(())
+			})?;
+		}
+
+		Ok(options)
+	}
+
+	/// The path to the `new` crate, `::new` unless set with
+	/// `#[new(crate = path)]`.
+	pub fn krate(&self) -> Path {
+		self.krate.clone().unwrap_or_else(|| parse_quote!(::new))
+	}
+
+	/// The constructor name with the given prefix, `new` unless renamed.
+	pub fn constructor(&self, prefix: &str) -> Ident {
+		self.name.as_ref().map_or_else(
+			|| format_ident!("{}new", prefix),
+			|name| format_ident!("{}{}", prefix, name),
+		)
+	}
+}
+
+/// How a field gets its value in a generated constructor.
+pub enum Kind {
+	/// Taken as an argument.
+	Arg { into: bool },
+	/// Filled with [`Default::default`].
+	Default,
+	/// Filled with the given expression.
+	DefaultExpr(Expr),
+}
+
+/// A struct field along with its `#[new(...)]` options.
+pub struct NewField<'a> {
+	pub member: Member,
+	pub binding: Ident,
+	pub ty: &'a Type,
+	pub kind: Kind,
+	pub validate: Option<Path>,
+	pub with: bool,
+}
+
+impl<'a> NewField<'a> {
+	fn parse(index: usize, field: &'a Field) -> Result<Self> {
+		let (member, binding) = field.ident.as_ref().map_or_else(
+			|| {
+				(
+					Member::from(index),
+					format_ident!("arg_{}", index, span = Span::call_site()),
+				)
+			},
+			|ident| (Member::from(ident.clone()), ident.clone()),
+		);
+
+		let mut into = false;
+		let mut default = None;
+		let mut validate = None;
+		let mut with = false;
+
+		for attr in field
+			.attrs
+			.iter()
+			.filter(|attr| attr.path().is_ident("new"))
+		{
+			attr.parse_nested_meta(|meta| {
+				if meta.path.is_ident("into") {
+					into = true;
+				} else if meta.path.is_ident("default") {
+					default = Some(if meta.input.peek(Token![=]) {
+						Kind::DefaultExpr(meta.value()?.parse()?)
+					} else {
+						Kind::Default
+					});
+				} else if meta.path.is_ident("validate") {
+					validate = Some(meta.value()?.parse()?);
+				} else if meta.path.is_ident("with") {
+					with = true;
+				} else {
+					return Err(meta.error("unknown `new` attribute"));
+				}
+
+				Ok(())
+			})?;
+		}
+
+		let kind = match default {
+			Some(_) if into => {
+				return Err(Error::new_spanned(
+					field,
+					"`into` cannot be combined with `default`",
+				))
+			}
+			Some(kind) => kind,
+			None => Kind::Arg { into },
+		};
+
+		Ok(Self {
+			member,
+			binding,
+			ty: &field.ty,
+			kind,
+			validate,
+			with,
+		})
+	}
+
+	/// The type a `with_*` constructor takes for this field, if it has one.
+	///
+	/// `Option<T>` fields take `T`, `#[new(with)]` fields take their own type.
+	pub fn with_ty(&self) -> Option<&'a Type> {
+		option_inner(self.ty).or_else(|| self.with.then_some(self.ty))
+	}
+
+	/// The field name as written, without any `r#` prefix.
+	pub fn member_name(&self) -> String {
+		match &self.member {
+			Member::Named(ident) => ident.unraw().to_string(),
+			Member::Unnamed(index) => index.index.to_string(),
+		}
+	}
+
+	/// The field name in `PascalCase`, for naming per-field items.
+	pub fn pascal_name(&self) -> Ident {
+		let name = match &self.member {
+			Member::Named(ident) => ident
+				.unraw()
+				.to_string()
+				.split('_')
+				.filter(|part| !part.is_empty())
+				.map(|part| {
+					let mut chars = part.chars();
+					chars.next().map_or_else(String::new, |first| {
+						first.to_uppercase().chain(chars).collect()
+					})
+				})
+				.collect(),
+			Member::Unnamed(index) => format!("Field{}", index.index),
+		};
+
+		Ident::new(&name, self.binding.span())
+	}
+
+	/// The constructor parameter for this field, if it takes one.
+	pub fn param(&self) -> Option<TokenStream> {
+		let Self { binding, ty, .. } = self;
+
+		match self.kind {
+			Kind::Arg { into: false } => Some(quote!(#binding: #ty)),
+			Kind::Arg { into: true } => Some(quote!(#binding: impl ::core::convert::Into<#ty>)),
+			_ => None,
+		}
+	}
+
+	/// The expression producing this field's value.
+	pub fn value(&self) -> TokenStream {
+		let binding = &self.binding;
+
+		match &self.kind {
+			Kind::Arg { into: false } => quote!(#binding),
+			Kind::Arg { into: true } => quote!(::core::convert::Into::into(#binding)),
+			Kind::Default => quote!(::core::default::Default::default()),
+			Kind::DefaultExpr(expr) => quote!(#expr),
+		}
+	}
+
+	/// The `member: value` initializer for this field.
+	pub fn init(&self) -> TokenStream {
+		let member = &self.member;
+		let value = self.value();
+
+		quote!(#member: #value)
+	}
+}
+
+/// The `T` in a field typed `Option<T>`.
+pub fn option_inner(ty: &Type) -> Option<&Type> {
+	let Type::Path(TypePath { qself: None, path }) = ty else {
+		return None;
+	};
+
+	let segment = path.segments.last()?;
+	if segment.ident != "Option" {
+		return None;
+	}
+
+	let PathArguments::AngleBracketed(args) = &segment.arguments else {
+		return None;
+	};
+
+	match args.args.first()? {
+		GenericArgument::Type(inner) if args.args.len() == 1 => Some(inner),
+		_ => None,
+	}
+}
+
+/// Collects the fields of a struct, rejecting enums and unions.
+pub fn fields(input: &DeriveInput) -> Result<Vec<NewField<'_>>> {
+	let Data::Struct(data) = &input.data else {
+		return Err(Error::new_spanned(
+			&input.ident,
+			"constructors can only be derived for structs",
+		));
+	};
+
+	data.fields
+		.iter()
+		.enumerate()
+		.map(|(index, field)| NewField::parse(index, field))
+		.collect()
+}
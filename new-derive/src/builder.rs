@@ -0,0 +1,285 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{ext::IdentExt, parse_quote, DeriveInput, Error, GenericParam, Ident, Result};
+
+use crate::attr::{self, option_inner, Kind, NewField, Options};
+
+/// How a builder stores and finishes a field.
+enum Slot {
+	/// Must be set before `build`, tracked by the given const parameter.
+	Required(Ident),
+	/// An `Option<T>` field, left as `None` when unset.
+	Optional,
+	/// Filled from its default when unset.
+	Defaulted,
+}
+
+impl Slot {
+	fn of(field: &NewField<'_>) -> Self {
+		match field.kind {
+			Kind::Default | Kind::DefaultExpr(_) => Self::Defaulted,
+			Kind::Arg { .. } if option_inner(field.ty).is_some() => Self::Optional,
+			Kind::Arg { .. } => {
+				let name = field.binding.unraw().to_string().to_uppercase();
+				Self::Required(format_ident!("{}_SET", name))
+			}
+		}
+	}
+
+	/// Binds the finished value of a field, running `missing` if a required
+	/// field is unset.
+	fn value(&self, field: &NewField<'_>, missing: &TokenStream) -> TokenStream {
+		let binding = &field.binding;
+
+		let value = match (self, &field.kind) {
+			(Self::Optional, _) => quote!(self.#binding),
+			(Self::Required(_), _) => quote! {
+				match self.#binding {
+					::core::option::Option::Some(value) => value,
+					::core::option::Option::None => #missing,
+				}
+			},
+			(_, Kind::DefaultExpr(expr)) => {
+				quote!(::core::option::Option::unwrap_or_else(self.#binding, || #expr))
+			}
+			_ => quote!(::core::option::Option::unwrap_or_default(self.#binding)),
+		};
+
+		quote!(let #binding = #value;)
+	}
+}
+
+pub fn expand(input: &DeriveInput) -> Result<TokenStream> {
+	let krate = Options::parse(&input.attrs)?.krate();
+	let fields = attr::fields(input)?;
+	let slots = fields.iter().map(Slot::of).collect::<Vec<_>>();
+
+	if let Some(field) = fields.iter().find(|field| {
+		matches!(
+			field.binding.unraw().to_string().as_str(),
+			"build" | "try_build"
+		)
+	}) {
+		return Err(Error::new(
+			field.binding.span(),
+			format!(
+				"a field named `{}` would clash with the builder's own method",
+				field.binding
+			),
+		));
+	}
+
+	let DeriveInput {
+		ident,
+		vis,
+		generics,
+		..
+	} = input;
+	let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+	let builder = format_ident!("{}Builder", ident);
+
+	let flags = slots
+		.iter()
+		.filter_map(|slot| match slot {
+			Slot::Required(flag) => Some(flag),
+			_ => None,
+		})
+		.collect::<Vec<_>>();
+
+	let mut builder_generics = generics.clone();
+	builder_generics.params.extend(
+		flags
+			.iter()
+			.map(|flag| -> GenericParam { parse_quote!(const #flag: bool) }),
+	);
+	let (builder_impl_generics, builder_ty_generics, _) = builder_generics.split_for_impl();
+
+	let params = generics.params.iter().map(|param| match param {
+		GenericParam::Lifetime(param) => {
+			let lifetime = &param.lifetime;
+			quote!(#lifetime)
+		}
+		GenericParam::Type(param) => {
+			let ident = &param.ident;
+			quote!(#ident)
+		}
+		GenericParam::Const(param) => {
+			let ident = &param.ident;
+			quote!(#ident)
+		}
+	});
+	let params = params.collect::<Vec<_>>();
+	let with_flags = |set: Option<&Ident>, value: bool| {
+		let flags = flags.iter().map(|&flag| {
+			if Some(flag) == set {
+				quote!(true)
+			} else if set.is_some() {
+				quote!(#flag)
+			} else {
+				quote!(#value)
+			}
+		});
+
+		quote!(#builder<#(#params,)* #(#flags),*>)
+	};
+	let unset = with_flags(None, false);
+	let complete = with_flags(None, true);
+
+	let storage = fields.iter().zip(&slots).map(|(field, slot)| {
+		let binding = &field.binding;
+		let ty = field.ty;
+
+		if matches!(slot, Slot::Optional) {
+			quote!(#binding: #ty)
+		} else {
+			quote!(#binding: ::core::option::Option<#ty>)
+		}
+	});
+	let bindings = fields
+		.iter()
+		.map(|field| &field.binding)
+		.collect::<Vec<_>>();
+
+	let setters = fields.iter().zip(&slots).map(|(field, slot)| {
+		let binding = &field.binding;
+		let ty = match slot {
+			Slot::Optional => option_inner(field.ty).unwrap_or(field.ty),
+			_ => field.ty,
+		};
+		let (param, value) = match field.kind {
+			Kind::Arg { into: true } => (
+				quote!(impl ::core::convert::Into<#ty>),
+				quote!(::core::convert::Into::into(#binding)),
+			),
+			_ => (quote!(#ty), quote!(#binding)),
+		};
+		let doc = format!("Sets `{}`.", field.member_name());
+
+		if let Slot::Required(flag) = slot {
+			let output = with_flags(Some(flag), true);
+			let others = bindings.iter().filter(|&&other| other != binding);
+
+			quote! {
+				#[doc = #doc]
+				#[must_use]
+				#vis fn #binding(self, #binding: #param) -> #output {
+					#builder {
+						#binding: ::core::option::Option::Some(#value),
+						#(#others: self.#others,)*
+					}
+				}
+			}
+		} else {
+			quote! {
+				#[doc = #doc]
+				#[must_use]
+				#vis fn #binding(mut self, #binding: #param) -> Self {
+					self.#binding = ::core::option::Option::Some(#value);
+					self
+				}
+			}
+		}
+	});
+
+	let values = fields
+		.iter()
+		.zip(&slots)
+		.map(|(field, slot)| {
+			let name = field.binding.unraw().to_string();
+
+			slot.value(
+				field,
+				&quote! {
+					return ::core::result::Result::Err(#krate::BuildError::Missing(#name))
+				},
+			)
+		})
+		.collect::<Vec<_>>();
+
+	let checks = fields
+		.iter()
+		.filter_map(|field| {
+			let validate = field.validate.as_ref()?;
+			let binding = &field.binding;
+			let name = field.binding.unraw().to_string();
+
+			Some(quote! {
+				if let ::core::result::Result::Err(source) = #validate(&#binding) {
+					return ::core::result::Result::Err(#krate::BuildError::Invalid {
+						field: #name,
+						source: ::core::convert::Into::into(source),
+					});
+				}
+			})
+		})
+		.collect::<Vec<_>>();
+
+	let inits = fields.iter().map(|field| {
+		let member = &field.member;
+		let binding = &field.binding;
+
+		quote!(#member: #binding)
+	});
+	let inits = quote!(#ident { #(#inits),* });
+
+	let build = checks.is_empty().then(|| {
+		let values = fields
+			.iter()
+			.zip(&slots)
+			.map(|(field, slot)| slot.value(field, &quote!(::core::unreachable!())));
+		let doc = format!("Builds the [`{ident}`].");
+
+		quote! {
+			impl #impl_generics #complete #where_clause {
+				#[doc = #doc]
+				#[must_use]
+				#vis fn build(self) -> #ident #ty_generics {
+					#(#values)*
+					#inits
+				}
+			}
+		}
+	});
+
+	let builder_doc = format!(
+		"A builder for [`{ident}`].\n\nThe const parameters track which required fields \
+		 have been set, so `build` only exists once all of them are."
+	);
+	let new_doc = format!("Creates a builder for [`{ident}`].");
+	let try_doc = format!("Builds the [`{ident}`], checking required fields and validators.");
+
+	Ok(quote! {
+		#[doc = #builder_doc]
+		#vis struct #builder #builder_impl_generics #where_clause {
+			#(#storage,)*
+		}
+
+		impl #impl_generics #ident #ty_generics #where_clause {
+			#[doc = #new_doc]
+			#[must_use]
+			#vis fn builder() -> #unset {
+				#builder {
+					#(#bindings: ::core::option::Option::None,)*
+				}
+			}
+		}
+
+		impl #builder_impl_generics #builder #builder_ty_generics #where_clause {
+			#(#setters)*
+
+			#[doc = #try_doc]
+			///
+			/// # Errors
+			///
+			/// Returns an error naming the first missing or invalid field.
+			#vis fn try_build(self) -> ::core::result::Result<#ident #ty_generics, #krate::BuildError> {
+				#(#values)*
+				#(#checks)*
+
+				::core::result::Result::Ok(#inits)
+			}
+		}
+
+		#build
+	})
+}
@@ -0,0 +1,111 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{
+	ext::IdentExt, parse_quote, Data, DataEnum, DeriveInput, Error, Fields, Generics, Path, Result,
+};
+
+use crate::attr::{self, Kind, Options};
+
+pub fn expand(input: &DeriveInput) -> Result<TokenStream> {
+	let ident = &input.ident;
+	let mut generics = input.generics.clone();
+	let krate = Options::parse(&input.attrs)?.krate();
+
+	let (error, body) = match &input.data {
+		Data::Enum(data) => expand_enum(input, data, &krate)?,
+		_ => expand_struct(input, &mut generics)?,
+	};
+	let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+	Ok(quote! {
+		impl #impl_generics ::core::str::FromStr for #ident #ty_generics #where_clause {
+			type Err = #error;
+
+			fn from_str(s: &str) -> ::core::result::Result<Self, Self::Err> {
+				#body
+			}
+		}
+	})
+}
+
+/// Parses the single argument field, filling the rest like `#[derive(New)]`.
+///
+/// The field's type is required to implement `FromStr` so generic newtypes
+/// work.
+fn expand_struct(
+	input: &DeriveInput,
+	generics: &mut Generics,
+) -> Result<(TokenStream, TokenStream)> {
+	let fields = attr::fields(input)?;
+
+	let mut args = fields
+		.iter()
+		.filter(|field| matches!(field.kind, Kind::Arg { .. }));
+	let (Some(arg), None) = (args.next(), args.next()) else {
+		return Err(Error::new_spanned(
+			&input.ident,
+			"`FromStr` needs exactly one field without `#[new(default)]`",
+		));
+	};
+
+	let binding = &arg.binding;
+	let ty = arg.ty;
+	generics
+		.make_where_clause()
+		.predicates
+		.push(parse_quote!(#ty: ::core::str::FromStr));
+	let inits = fields.iter().map(|field| {
+		if std::ptr::eq(field, arg) {
+			let member = &field.member;
+
+			quote!(#member: #binding)
+		} else {
+			field.init()
+		}
+	});
+
+	Ok((
+		quote!(<#ty as ::core::str::FromStr>::Err),
+		quote! {
+			let #binding = <#ty as ::core::str::FromStr>::from_str(s)?;
+
+			::core::result::Result::Ok(Self { #(#inits),* })
+		},
+	))
+}
+
+/// Matches the variant names of a fieldless enum.
+fn expand_enum(
+	input: &DeriveInput,
+	data: &DataEnum,
+	krate: &Path,
+) -> Result<(TokenStream, TokenStream)> {
+	let arms = data
+		.variants
+		.iter()
+		.map(|variant| {
+			if !matches!(variant.fields, Fields::Unit) {
+				return Err(Error::new_spanned(
+					variant,
+					"`FromStr` can only be derived for enums without fields",
+				));
+			}
+
+			let ident = &variant.ident;
+			let name = ident.unraw().to_string();
+
+			Ok(quote!(#name => ::core::result::Result::Ok(Self::#ident)))
+		})
+		.collect::<Result<Vec<_>>>()?;
+	let type_name = input.ident.unraw().to_string();
+
+	Ok((
+		quote!(#krate::ParseVariantError),
+		quote! {
+			match s {
+				#(#arms,)*
+				_ => ::core::result::Result::Err(#krate::ParseVariantError::new(#type_name)),
+			}
+		},
+	))
+}
@@ -0,0 +1,130 @@
+//! Derive macros for the `new` crate.
+
+mod attr;
+mod builder;
+mod from_str;
+mod new;
+mod try_new;
+mod with;
+
+use proc_macro::TokenStream;
+use syn::{parse_macro_input, DeriveInput};
+
+/// Derives a `new` constructor taking one argument per field.
+///
+/// Fields can be tuned with `#[new(...)]` attributes:
+///
+/// - `#[new(default)]` skips the field and fills it with [`Default::default`].
+/// - `#[new(default = expr)]` skips the field and fills it with `expr`.
+/// - `#[new(into)]` takes the argument as `impl Into<T>`.
+///
+/// The struct itself accepts `#[new(name = ident)]` to generate `ident`
+/// instead of `new`, and `#[new(crate = path)]` for when the `new` crate is
+/// renamed or re-exported. Every derive here but [`With`] generates paths
+/// into it, `::new` by default.
+///
+/// The constructor is a `const fn` when every field is a plain argument.
+///
+/// A `<Struct>NewArgs` struct with one public field per argument is generated
+/// alongside, implementing `new::NamedNew` so `new!(Struct { name: value })`
+/// works too. Named arguments take the field's own type, even for
+/// `#[new(into)]` fields.
+///
+/// `new::New` is implemented for the tuple of argument types, again using
+/// each field's own type, so generic code can bound on the constructor.
+#[proc_macro_derive(New, attributes(new))]
+pub fn derive_new(input: TokenStream) -> TokenStream {
+	let input = parse_macro_input!(input as DeriveInput);
+
+	new::expand(&input)
+		.unwrap_or_else(syn::Error::into_compile_error)
+		.into()
+}
+
+/// Derives a fallible `try_new` constructor that validates its fields.
+///
+/// Accepts the same attributes as [`New`], plus `#[new(validate = path)]` on
+/// fields. The validator is called as `path(&value)` and returns a
+/// `Result<(), E>` where `E` converts into a boxed error.
+///
+/// A `<Struct>NewError` enum is generated alongside, with one variant per
+/// validated field carrying the validator's error as its source. With
+/// `#[new(name = ident)]` the constructor is named `try_<ident>`.
+///
+/// `new::TryNew` is implemented for the tuple of argument types, with the
+/// generated enum as its error.
+///
+/// Validated fields box their errors, so they need `new`'s `alloc` feature.
+#[proc_macro_derive(TryNew, attributes(new))]
+pub fn derive_try_new(input: TokenStream) -> TokenStream {
+	let input = parse_macro_input!(input as DeriveInput);
+
+	try_new::expand(&input)
+		.unwrap_or_else(syn::Error::into_compile_error)
+		.into()
+}
+
+/// Derives `with_<field>` constructors and `and_<field>` setters.
+///
+/// Every `Option<T>` field, and every field marked `#[new(with)]`, gets:
+///
+/// - `with_<field>(value, args...)`, taking the field's value followed by the
+///   arguments [`New`] would take for the remaining fields, so that
+///   `with!(Type: field(value, args...))` works.
+/// - `and_<field>(self, value) -> Self`, a chainable setter. It can't share
+///   the `with_` name since Rust has no overloading.
+///
+/// `Option<T>` fields take a `T` and are set to `Some`. Tuple fields are
+/// named `arg_<index>`, as in `with_arg_0`, like the other derives name them.
+#[proc_macro_derive(With, attributes(new))]
+pub fn derive_with(input: TokenStream) -> TokenStream {
+	let input = parse_macro_input!(input as DeriveInput);
+
+	with::expand(&input)
+		.unwrap_or_else(syn::Error::into_compile_error)
+		.into()
+}
+
+/// Derives a type-state builder, `<Struct>Builder`, and a `builder()`
+/// constructor for it.
+///
+/// Fields are treated like [`New`] treats them: `Option<T>` fields and
+/// `#[new(default)]` fields are optional, every other field is required.
+/// Each field gets a setter named after it, or `arg_<index>` for tuple
+/// structs, taking `impl Into<T>` for `#[new(into)]` fields. Fields named
+/// `build` or `try_build` are rejected, since their setters would clash.
+///
+/// `build()` is only available once every required field has been set, so a
+/// missing field is a compile error. `try_build()` is available in any state
+/// and also runs `#[new(validate = path)]` validators, returning a
+/// `new::BuildError` naming fields like their setters. Structs with
+/// validators only get `try_build()`.
+///
+/// `new::BuildError` needs `new`'s `alloc` feature.
+#[proc_macro_derive(Builder, attributes(new))]
+pub fn derive_builder(input: TokenStream) -> TokenStream {
+	let input = parse_macro_input!(input as DeriveInput);
+
+	builder::expand(&input)
+		.unwrap_or_else(syn::Error::into_compile_error)
+		.into()
+}
+
+/// Derives [`FromStr`] for newtypes and fieldless enums.
+///
+/// Structs parse their one argument field with its own `FromStr`, returning
+/// its error. Other fields must be `#[new(default)]` and are filled the way
+/// [`New`] fills them.
+///
+/// Enums match the input against their variant names exactly, returning a
+/// `new::ParseVariantError` when nothing matches.
+///
+/// [`FromStr`]: core::str::FromStr
+#[proc_macro_derive(FromStr, attributes(new))]
+pub fn derive_from_str(input: TokenStream) -> TokenStream {
+	let input = parse_macro_input!(input as DeriveInput);
+
+	from_str::expand(&input)
+		.unwrap_or_else(syn::Error::into_compile_error)
+		.into()
+}
@@ -0,0 +1,143 @@
+use std::collections::HashSet;
+
+use proc_macro2::{TokenStream, TokenTree};
+use quote::{format_ident, quote, ToTokens};
+use syn::{DeriveInput, GenericParam, Generics, Ident, Result};
+
+use crate::attr::{self, Kind, Options};
+
+pub fn expand(input: &DeriveInput) -> Result<TokenStream> {
+	let options = Options::parse(&input.attrs)?;
+	let fields = attr::fields(input)?;
+
+	let DeriveInput {
+		ident,
+		vis,
+		generics,
+		..
+	} = input;
+	let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+	let krate = options.krate();
+	let constructor = options.constructor("");
+	let params = fields.iter().filter_map(attr::NewField::param);
+	let inits = fields.iter().map(attr::NewField::init);
+	let constness = fields
+		.iter()
+		.all(|field| matches!(field.kind, Kind::Arg { into: false }))
+		.then(|| quote!(const));
+	let doc = format!("Creates a new [`{ident}`].");
+
+	let args = fields
+		.iter()
+		.filter(|field| matches!(field.kind, Kind::Arg { .. }))
+		.collect::<Vec<_>>();
+	let args_ident = format_ident!("{}NewArgs", ident);
+	let args_generics = used_generics(generics, args.iter().map(|field| field.ty));
+	let (args_impl_generics, args_ty_generics, args_where_clause) = args_generics.split_for_impl();
+	let args_fields = args.iter().map(|field| {
+		let binding = &field.binding;
+		let ty = field.ty;
+		let doc = format!("The `{}` argument.", field.member_name());
+
+		quote! {
+			#[doc = #doc]
+			pub #binding: #ty
+		}
+	});
+	let args_values = args.iter().map(|field| {
+		let binding = &field.binding;
+
+		quote!(args.#binding)
+	});
+	let tuple_bindings = args.iter().map(|field| &field.binding);
+	let tuple_bindings = quote!((#(#tuple_bindings,)*));
+	let tuple_types = args.iter().map(|field| field.ty);
+	let tuple_types = quote!((#(#tuple_types,)*));
+	let args_doc = format!(
+		"Named arguments for [`{ident}::{constructor}`], used by `new!({ident} {{ .. }})`."
+	);
+
+	Ok(quote! {
+		impl #impl_generics #ident #ty_generics #where_clause {
+			#[doc = #doc]
+			#[must_use]
+			#vis #constness fn #constructor(#(#params),*) -> Self {
+				Self { #(#inits),* }
+			}
+		}
+
+		#[doc = #args_doc]
+		#vis struct #args_ident #args_impl_generics #args_where_clause {
+			#(#args_fields,)*
+		}
+
+		impl #impl_generics #krate::New<#tuple_types> for #ident #ty_generics #where_clause {
+			fn new(#tuple_bindings: #tuple_types) -> Self {
+				Self::#constructor #tuple_bindings
+			}
+		}
+
+		impl #impl_generics #krate::NamedNew for #ident #ty_generics #where_clause {
+			type Args = #args_ident #args_ty_generics;
+
+			fn new_named(args: Self::Args) -> Self {
+				Self::#constructor(#(#args_values),*)
+			}
+		}
+	})
+}
+
+/// Keeps the generic parameters mentioned by the given types, along with the
+/// where predicates that only mention those.
+fn used_generics<'a>(generics: &Generics, types: impl Iterator<Item = &'a syn::Type>) -> Generics {
+	fn collect(tokens: TokenStream, idents: &mut HashSet<Ident>) {
+		for token in tokens {
+			match token {
+				TokenTree::Ident(ident) => {
+					idents.insert(ident);
+				}
+				TokenTree::Group(group) => collect(group.stream(), idents),
+				_ => {}
+			}
+		}
+	}
+
+	let param_ident = |param: &GenericParam| match param {
+		GenericParam::Lifetime(param) => param.lifetime.ident.clone(),
+		GenericParam::Type(param) => param.ident.clone(),
+		GenericParam::Const(param) => param.ident.clone(),
+	};
+
+	let mut mentioned = HashSet::new();
+	for ty in types {
+		collect(ty.to_token_stream(), &mut mentioned);
+	}
+
+	let (kept, dropped): (Vec<_>, Vec<_>) = generics
+		.params
+		.iter()
+		.cloned()
+		.partition(|param| mentioned.contains(&param_ident(param)));
+	let dropped = dropped.iter().map(param_ident).collect::<HashSet<_>>();
+
+	let mut used = Generics {
+		params: kept.into_iter().collect(),
+		..generics.clone()
+	};
+
+	if let Some(where_clause) = &mut used.where_clause {
+		where_clause.predicates = where_clause
+			.predicates
+			.iter()
+			.filter(|predicate| {
+				let mut idents = HashSet::new();
+				collect(predicate.to_token_stream(), &mut idents);
+				idents.is_disjoint(&dropped)
+			})
+			.cloned()
+			.collect();
+	}
+
+	used
+}
@@ -0,0 +1,130 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{DeriveInput, Result};
+
+use crate::attr::{self, Options};
+
+pub fn expand(input: &DeriveInput) -> Result<TokenStream> {
+	let options = Options::parse(&input.attrs)?;
+	let fields = attr::fields(input)?;
+
+	let DeriveInput {
+		ident,
+		vis,
+		generics,
+		..
+	} = input;
+	let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+	let error = format_ident!("{}NewError", ident);
+	let krate = options.krate();
+	let constructor = options.constructor("try_");
+	let params = fields.iter().filter_map(attr::NewField::param);
+
+	let bindings = fields.iter().map(|field| {
+		let binding = &field.binding;
+		let ty = field.ty;
+		let value = field.value();
+
+		quote!(let #binding: #ty = #value;)
+	});
+
+	let validated = fields
+		.iter()
+		.filter_map(|field| Some((field, field.validate.as_ref()?, field.pascal_name())))
+		.collect::<Vec<_>>();
+
+	let checks = validated.iter().map(|(field, validate, variant)| {
+		let binding = &field.binding;
+
+		quote! {
+			if let ::core::result::Result::Err(source) = #validate(&#binding) {
+				return ::core::result::Result::Err(#error::#variant(::core::convert::Into::into(source)));
+			}
+		}
+	});
+
+	let inits = fields.iter().map(|field| {
+		let member = &field.member;
+		let binding = &field.binding;
+
+		quote!(#member: #binding)
+	});
+
+	let variants = validated.iter().map(|(field, _, variant)| {
+		let doc = format!("`{}` failed validation.", field.member_name());
+
+		quote! {
+			#[doc = #doc]
+			#variant(#krate::__private::Box<dyn ::core::error::Error + ::core::marker::Send + ::core::marker::Sync>)
+		}
+	});
+
+	let messages = validated.iter().map(|(field, _, variant)| {
+		let message = format!("invalid value for `{}`", field.member_name());
+
+		quote!(Self::#variant(_) => f.write_str(#message))
+	});
+
+	let sources = validated.iter().map(
+		|(_, _, variant)| quote!(Self::#variant(ref source) => ::core::option::Option::Some(&**source)),
+	);
+
+	let args = fields
+		.iter()
+		.filter(|field| matches!(field.kind, attr::Kind::Arg { .. }))
+		.collect::<Vec<_>>();
+	let tuple_bindings = args.iter().map(|field| &field.binding);
+	let tuple_bindings = quote!((#(#tuple_bindings,)*));
+	let tuple_types = args.iter().map(|field| field.ty);
+	let tuple_types = quote!((#(#tuple_types,)*));
+
+	let error_doc = format!("The error returned by [`{ident}::{constructor}`].");
+	let doc = format!("Creates a new [`{ident}`], validating its fields.");
+
+	Ok(quote! {
+		#[doc = #error_doc]
+		#[derive(Debug)]
+		#vis enum #error {
+			#(#variants,)*
+		}
+
+		impl ::core::fmt::Display for #error {
+			fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+				match *self {
+					#(#messages,)*
+				}
+			}
+		}
+
+		impl ::core::error::Error for #error {
+			fn source(&self) -> ::core::option::Option<&(dyn ::core::error::Error + 'static)> {
+				match *self {
+					#(#sources,)*
+				}
+			}
+		}
+
+		impl #impl_generics #ident #ty_generics #where_clause {
+			#[doc = #doc]
+			///
+			/// # Errors
+			///
+			/// Returns an error naming the first field that fails validation.
+			#vis fn #constructor(#(#params),*) -> ::core::result::Result<Self, #error> {
+				#(#bindings)*
+				#(#checks)*
+
+				::core::result::Result::Ok(Self { #(#inits),* })
+			}
+		}
+
+		impl #impl_generics #krate::TryNew<#tuple_types> for #ident #ty_generics #where_clause {
+			type Error = #error;
+
+			fn try_new(#tuple_bindings: #tuple_types) -> ::core::result::Result<Self, Self::Error> {
+				Self::#constructor #tuple_bindings
+			}
+		}
+	})
+}
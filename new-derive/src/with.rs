@@ -0,0 +1,69 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{ext::IdentExt, DeriveInput, Result};
+
+use crate::attr;
+
+pub fn expand(input: &DeriveInput) -> Result<TokenStream> {
+	let fields = attr::fields(input)?;
+
+	let DeriveInput {
+		ident,
+		vis,
+		generics,
+		..
+	} = input;
+	let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+	let methods = fields.iter().enumerate().filter_map(|(index, field)| {
+		let ty = field.with_ty()?;
+		let name = field.member_name();
+		let member = &field.member;
+		let binding = &field.binding;
+		let value = if std::ptr::eq(ty, field.ty) {
+			quote!(#binding)
+		} else {
+			quote!(::core::option::Option::Some(#binding))
+		};
+
+		let others = fields
+			.iter()
+			.enumerate()
+			.filter(|&(other, _)| other != index)
+			.map(|(_, field)| field);
+		let params = others.clone().filter_map(attr::NewField::param);
+		let inits = others.map(attr::NewField::init);
+
+		// Named after the binding, so tuple fields match their `arg_<index>`
+		// names in the other derives.
+		let suffix = binding.unraw();
+		let constructor = format_ident!("with_{}", suffix);
+		let setter = format_ident!("and_{}", suffix);
+		let constructor_doc = format!("Creates a new [`{ident}`] with `{name}` set.");
+		let setter_doc = format!("Sets `{name}`, returning the updated value.");
+
+		Some(quote! {
+			#[doc = #constructor_doc]
+			#[must_use]
+			#vis fn #constructor(#binding: #ty, #(#params),*) -> Self {
+				Self {
+					#member: #value,
+					#(#inits),*
+				}
+			}
+
+			#[doc = #setter_doc]
+			#[must_use]
+			#vis fn #setter(mut self, #binding: #ty) -> Self {
+				self.#member = #value;
+				self
+			}
+		})
+	});
+
+	Ok(quote! {
+		impl #impl_generics #ident #ty_generics #where_clause {
+			#(#methods)*
+		}
+	})
+}
@@ -0,0 +1,143 @@
+//! Tests for `#[derive(Builder)]`.
+
+use std::{error::Error, time::Duration};
+
+use new::{builder, BuildError, Builder};
+
+#[derive(Debug, PartialEq, Eq, Builder)]
+struct Config {
+	#[new(into)]
+	host: String,
+	port: u16,
+	timeout: Option<Duration>,
+	#[new(default = 3)]
+	retries: u8,
+	#[new(default)]
+	verbose: bool,
+}
+
+#[derive(Debug, PartialEq, Eq, Builder)]
+struct Pair<T: Clone, U> {
+	left: T,
+	right: Option<U>,
+}
+
+#[derive(Debug, PartialEq, Eq, Builder)]
+struct Tuple(u8, Option<u8>);
+
+const fn non_empty(value: &str) -> Result<(), &'static str> {
+	if value.is_empty() {
+		Err("empty")
+	} else {
+		Ok(())
+	}
+}
+
+#[derive(Debug, PartialEq, Eq, Builder)]
+struct User {
+	#[new(validate = non_empty)]
+	name: String,
+	age: Option<u8>,
+}
+
+#[test]
+fn builders_work() {
+	assert_eq!(
+		Config::builder().port(80).host("localhost").build(),
+		Config {
+			host: "localhost".to_owned(),
+			port: 80,
+			timeout: None,
+			retries: 3,
+			verbose: false,
+		}
+	);
+
+	assert_eq!(
+		Config::builder()
+			.host("localhost")
+			.port(80)
+			.timeout(Duration::from_secs(1))
+			.retries(5)
+			.verbose(true)
+			.build(),
+		Config {
+			host: "localhost".to_owned(),
+			port: 80,
+			timeout: Some(Duration::from_secs(1)),
+			retries: 5,
+			verbose: true,
+		}
+	);
+}
+
+#[test]
+fn generic_builders_work() {
+	assert_eq!(
+		Pair::<u8, &str>::builder().left(1).build(),
+		Pair {
+			left: 1,
+			right: None
+		}
+	);
+}
+
+#[test]
+fn tuple_builders_work() {
+	assert_eq!(Tuple::builder().arg_0(1).build(), Tuple(1, None));
+	assert_eq!(
+		Tuple::builder().arg_1(2).arg_0(1).build(),
+		Tuple(1, Some(2))
+	);
+	assert!(matches!(
+		Tuple::builder().try_build(),
+		Err(BuildError::Missing("arg_0"))
+	));
+}
+
+#[test]
+fn try_build_reports_missing_fields() {
+	let err = Config::builder().port(80).try_build().unwrap_err();
+	assert!(matches!(err, BuildError::Missing("host")));
+	assert_eq!(err.to_string(), "missing required field `host`");
+}
+
+#[test]
+fn try_build_runs_validators() {
+	let err = User::builder().name(String::new()).try_build().unwrap_err();
+	assert!(matches!(err, BuildError::Invalid { field: "name", .. }));
+	assert_eq!(err.source().unwrap().to_string(), "empty");
+
+	assert_eq!(
+		User::builder()
+			.name("Ferris".to_owned())
+			.age(7)
+			.try_build()
+			.ok(),
+		Some(User {
+			name: "Ferris".to_owned(),
+			age: Some(7),
+		})
+	);
+}
+
+#[test]
+fn builder_macro_works() {
+	assert_eq!(
+		builder!(Config {
+			host: "localhost",
+			port: 80,
+			verbose: true,
+		}),
+		Config::builder()
+			.host("localhost")
+			.port(80)
+			.verbose(true)
+			.build()
+	);
+
+	let partial = builder!(Config { port: 80, .. });
+	assert_eq!(partial.host("localhost").build().port, 80);
+
+	assert_eq!(builder!(Pair<u8, u8> { left: 1, right: 2 }).right, Some(2));
+}
@@ -0,0 +1,51 @@
+//! Tests for `#[derive(FromStr)]`.
+
+use std::num::{NonZero, ParseIntError};
+
+use new::{parse, FromStr, ParseVariantError};
+
+#[derive(Debug, PartialEq, Eq, FromStr)]
+struct Id(u32);
+
+#[derive(Debug, PartialEq, Eq, FromStr)]
+struct Port {
+	number: NonZero<u16>,
+	#[new(default = "tcp")]
+	protocol: &'static str,
+}
+
+#[derive(Debug, PartialEq, Eq, FromStr)]
+struct Wrapper<T>(T);
+
+#[derive(Debug, PartialEq, Eq, FromStr)]
+enum Level {
+	Low,
+	High,
+	r#Max,
+}
+
+#[test]
+fn newtypes_parse() {
+	assert_eq!(parse!(Id: "7"), Ok(Id(7)));
+	assert!(matches!(parse!(Id: "seven"), Err(ParseIntError { .. })));
+	assert_eq!(
+		parse!(Port: "8080"),
+		Ok(Port {
+			number: NonZero::new(8080).unwrap(),
+			protocol: "tcp",
+		})
+	);
+	assert!(parse!(Port: "0").is_err());
+	assert_eq!(parse!(Wrapper<bool>: "true"), Ok(Wrapper(true)));
+}
+
+#[test]
+fn enums_parse() {
+	assert_eq!(parse!(Level: "Low"), Ok(Level::Low));
+	assert_eq!(parse!(Level: "High"), Ok(Level::High));
+	assert_eq!(parse!(Level: "Max"), Ok(Level::Max));
+
+	let error = parse!(Level: "low").unwrap_err();
+	assert_eq!(error, ParseVariantError::new("Level"));
+	assert_eq!(error.to_string(), "unknown `Level` variant");
+}
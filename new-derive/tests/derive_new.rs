@@ -0,0 +1,124 @@
+//! Tests for `#[derive(New)]`.
+
+use new::{new, New};
+
+#[derive(Debug, Clone, PartialEq, New)]
+struct ManyArgs {
+	value: u8,
+	#[new(default)]
+	thing: Option<String>,
+	other: bool,
+	floating: f32,
+}
+
+#[derive(Debug, PartialEq, Eq, New)]
+struct Named(#[new(into)] String, #[new(default = 7)] u8);
+
+#[derive(Debug, PartialEq, Eq, New)]
+struct Pair<T, U>
+where
+	U: Clone,
+{
+	left: T,
+	right: U,
+	#[new(default)]
+	count: usize,
+}
+
+#[derive(Debug, PartialEq, Eq, New)]
+struct Unit;
+
+#[derive(Debug, PartialEq, Eq, New)]
+struct Point {
+	x: i32,
+	y: i32,
+}
+
+const ORIGIN: Point = new!(Point(0, 0));
+
+#[test]
+fn named_fields_work() {
+	assert_eq!(
+		new!(ManyArgs(8u8, true, 7.0)),
+		ManyArgs {
+			value: 8,
+			thing: None,
+			other: true,
+			floating: 7.0,
+		}
+	);
+}
+
+#[test]
+fn tuple_fields_work() {
+	assert_eq!(new!(Named("hello")), Named("hello".to_owned(), 7));
+}
+
+#[test]
+#[rustfmt::skip]
+fn generic_fields_work() {
+	assert_eq!(
+		new!(Pair<u8, &str>(1, "right")),
+		Pair {
+			left: 1,
+			right: "right",
+			count: 0,
+		}
+	);
+}
+
+#[test]
+fn unit_structs_work() {
+	assert_eq!(new!(Unit()), Unit);
+}
+
+#[test]
+fn constructors_are_const() {
+	assert_eq!(ORIGIN, Point { x: 0, y: 0 });
+}
+
+#[test]
+fn named_arguments_work() {
+	assert_eq!(
+		new!(ManyArgs {
+			floating: 7.0,
+			value: 8,
+			other: true,
+		}),
+		new!(ManyArgs(8, true, 7.0))
+	);
+
+	let x = 1;
+	assert_eq!(new!(Point { y: 2, x }), Point { x: 1, y: 2 });
+	assert_eq!(
+		new!(Named {
+			arg_0: "hello".to_owned()
+		}),
+		Named("hello".to_owned(), 7)
+	);
+	assert_eq!(new!(Unit {}), Unit);
+}
+
+#[test]
+#[rustfmt::skip]
+fn named_generic_arguments_work() {
+	fn make<T: Clone>(left: T) -> Pair<T, &'static str> {
+		new!(Pair<T, _> { right: "right", left })
+	}
+
+	assert_eq!(make(1u8), new!(Pair<u8, &str>(1, "right")));
+}
+
+#[test]
+fn trait_constructors_work() {
+	fn make<T: New<(u8, bool, f32)>>() -> T {
+		new!(T as New(8, true, 7.0))
+	}
+
+	assert_eq!(make::<ManyArgs>(), new!(ManyArgs(8, true, 7.0)));
+	assert_eq!(
+		new!(Named as New("hello".to_owned())),
+		Named("hello".to_owned(), 7)
+	);
+	assert_eq!(new!(Unit as New()), Unit);
+}
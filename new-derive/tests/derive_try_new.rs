@@ -0,0 +1,91 @@
+//! Tests for `#[derive(TryNew)]`.
+
+use std::{error::Error, fmt, num::ParseIntError};
+
+use new::{new, try_new, New, TryNew};
+
+#[derive(Debug, PartialEq, Eq)]
+struct OutOfRange;
+
+impl fmt::Display for OutOfRange {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str("value out of range")
+	}
+}
+
+impl Error for OutOfRange {}
+
+#[allow(clippy::trivially_copy_pass_by_ref)] // validators take their field by reference
+const fn percentage(value: &u8) -> Result<(), OutOfRange> {
+	if *value > 100 {
+		Err(OutOfRange)
+	} else {
+		Ok(())
+	}
+}
+
+fn numeric(value: &str) -> Result<(), ParseIntError> {
+	value.parse::<u32>().map(drop)
+}
+
+#[derive(Debug, PartialEq, Eq, TryNew)]
+struct Progress {
+	#[new(validate = percentage)]
+	percent: u8,
+	#[new(into, validate = numeric)]
+	id: String,
+	#[new(default)]
+	done: bool,
+}
+
+#[derive(Debug, PartialEq, Eq, New, TryNew)]
+#[new(name = from_parts)]
+struct Ratio(#[new(validate = percentage)] u8, u8);
+
+#[test]
+fn valid_values_work() -> Result<(), ProgressNewError> {
+	assert_eq!(
+		try_new!(Progress(42, "7"))?,
+		Progress {
+			percent: 42,
+			id: "7".to_owned(),
+			done: false,
+		}
+	);
+
+	Ok(())
+}
+
+#[test]
+fn invalid_values_name_the_field() {
+	let err = try_new!(Progress(101, "7")).unwrap_err();
+	assert!(matches!(err, ProgressNewError::Percent(_)));
+	assert_eq!(err.to_string(), "invalid value for `percent`");
+	assert_eq!(err.source().unwrap().to_string(), "value out of range");
+
+	let err = try_new!(Progress(42, "seven")).unwrap_err();
+	assert!(matches!(err, ProgressNewError::Id(_)));
+	assert!(err.source().unwrap().is::<ParseIntError>());
+}
+
+#[test]
+fn named_constructors_work() {
+	assert_eq!(Ratio::from_parts(1, 2), Ratio(1, 2));
+	assert_eq!(try_new!(Ratio: from_parts(1, 2)).ok(), Some(Ratio(1, 2)));
+	assert!(matches!(
+		try_new!(Ratio: from_parts(200, 2)),
+		Err(RatioNewError::Field0(_))
+	));
+}
+
+#[test]
+fn trait_constructors_work() {
+	fn make<T: TryNew<(u8, u8)>>(left: u8) -> Result<T, T::Error> {
+		try_new!(T as TryNew(left, 2))
+	}
+
+	assert_eq!(make::<Ratio>(1).ok(), Some(Ratio(1, 2)));
+	assert!(matches!(make::<Ratio>(200), Err(RatioNewError::Field0(_))));
+	assert!(try_new!(Progress as TryNew(42, "7".to_owned())).is_ok());
+	assert_eq!(new!(Ratio as New(1, 2)), Ratio(1, 2));
+}
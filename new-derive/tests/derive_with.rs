@@ -0,0 +1,71 @@
+//! Tests for `#[derive(With)]`.
+
+use new::{new, with, New, With};
+
+#[derive(Debug, Clone, PartialEq, New, With)]
+struct ManyArgs {
+	value: u8,
+	#[new(default)]
+	thing: Option<String>,
+	other: bool,
+	floating: f32,
+}
+
+#[derive(Debug, PartialEq, Eq, New, With)]
+struct Limits {
+	#[new(default = 16, with)]
+	depth: usize,
+	#[new(default)]
+	width: Option<u32>,
+	#[new(default)]
+	height: Option<u32>,
+}
+
+#[derive(Debug, PartialEq, Eq, With)]
+struct Wrapper<T>(Option<T>, bool);
+
+#[test]
+fn with_constructors_work() {
+	assert_eq!(
+		with!(ManyArgs: thing("Hello, world!".to_owned(), 8u8, true, 7.0)),
+		ManyArgs {
+			thing: Some("Hello, world!".to_owned()),
+			value: 8,
+			other: true,
+			floating: 7.0,
+		}
+	);
+
+	assert_eq!(
+		with!(Limits: depth(4)),
+		Limits {
+			depth: 4,
+			width: None,
+			height: None,
+		}
+	);
+	assert_eq!(with!(Limits: width(7)).width, Some(7));
+	assert_eq!(Wrapper::with_arg_0(7u8, true), Wrapper(Some(7), true));
+	assert_eq!(
+		Wrapper::with_arg_0(7u8, true).and_arg_0(8),
+		Wrapper(Some(8), true)
+	);
+}
+
+#[test]
+fn setters_chain() {
+	assert_eq!(
+		new!(Limits()).and_width(1).and_height(2).and_depth(3),
+		Limits {
+			depth: 3,
+			width: Some(1),
+			height: Some(2),
+		}
+	);
+	assert_eq!(
+		new!(ManyArgs(8u8, true, 7.0))
+			.and_thing("thing".to_owned())
+			.thing,
+		Some("thing".to_owned())
+	);
+}
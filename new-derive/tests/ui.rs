@@ -0,0 +1,6 @@
+//! Checks that the derives reject invalid input with useful errors.
+
+#[test]
+fn ui() {
+	trybuild::TestCases::new().compile_fail("tests/ui/*.rs");
+}
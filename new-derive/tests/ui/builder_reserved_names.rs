@@ -0,0 +1,9 @@
+use new::Builder;
+
+#[derive(Builder)]
+struct Job {
+	name: String,
+	build: bool,
+}
+
+fn main() {}
@@ -0,0 +1,96 @@
+use proc_macro2::{Delimiter, Group, Ident, Spacing, Span, TokenStream, TokenTree};
+use syn::{ext::IdentExt, Error, Lit, Result};
+
+pub fn expand(input: TokenStream) -> Result<TokenStream> {
+	let mut output = TokenStream::new();
+
+	for token in input {
+		match token {
+			TokenTree::Group(group) => {
+				if let Some(ident) = joined(&group)? {
+					output.extend([TokenTree::Ident(ident)]);
+				} else {
+					let mut inner = Group::new(group.delimiter(), expand(group.stream())?);
+					inner.set_span(group.span());
+					output.extend([TokenTree::Group(inner)]);
+				}
+			}
+			token => output.extend([token]),
+		}
+	}
+
+	Ok(output)
+}
+
+/// The identifier for a `[<segments>]` group, or `None` for other groups.
+fn joined(group: &Group) -> Result<Option<Ident>> {
+	if group.delimiter() != Delimiter::Bracket {
+		return Ok(None);
+	}
+
+	let tokens = group.stream().into_iter().collect::<Vec<_>>();
+	let segments = match tokens.as_slice() {
+		[TokenTree::Punct(open), segments @ .., TokenTree::Punct(close)]
+			if open.as_char() == '<'
+				&& open.spacing() == Spacing::Alone
+				&& close.as_char() == '>' =>
+		{
+			segments
+		}
+		_ => return Ok(None),
+	};
+
+	let mut name = String::new();
+	let mut span = None;
+
+	for segment in segments {
+		push_segment(&mut name, &mut span, segment)?;
+	}
+
+	let valid = name
+		.chars()
+		.next()
+		.is_some_and(|c| c == '_' || c.is_alphabetic())
+		&& name.chars().all(|c| c == '_' || c.is_alphanumeric())
+		&& name != "_";
+
+	if !valid {
+		return Err(Error::new(
+			group.span(),
+			format!("`{name}` is not a valid identifier"),
+		));
+	}
+
+	let span = Span::call_site().located_at(span.unwrap_or_else(|| group.span()));
+
+	Ok(Some(Ident::new(&name, span)))
+}
+
+/// Appends one segment to `name`, recording the span of the first.
+fn push_segment(name: &mut String, span: &mut Option<Span>, segment: &TokenTree) -> Result<()> {
+	let text = match segment {
+		// Fragments like `$prefix:literal` arrive in invisible groups.
+		TokenTree::Group(group) if group.delimiter() == Delimiter::None => {
+			for segment in group.stream() {
+				push_segment(name, span, &segment)?;
+			}
+
+			return Ok(());
+		}
+		TokenTree::Ident(ident) => ident.unraw().to_string(),
+		TokenTree::Punct(punct) if punct.as_char() == '_' => "_".to_owned(),
+		TokenTree::Literal(literal) => match Lit::new(literal.clone()) {
+			Lit::Str(lit) => lit.value(),
+			Lit::Int(lit) => lit.base10_digits().to_owned(),
+			_ => return Err(Error::new(literal.span(), "expected a string or integer")),
+		},
+		TokenTree::Punct(_) | TokenTree::Group(_) => {
+			return Err(Error::new(segment.span(), "expected an identifier segment"));
+		}
+	};
+
+	span.get_or_insert_with(|| segment.span());
+	name.push_str(&text);
+
+	Ok(())
+}
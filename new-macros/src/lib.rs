@@ -0,0 +1,30 @@
+//! Support macros for the `new` crate.
+
+mod join;
+mod located;
+
+use proc_macro::TokenStream;
+use syn::parse_macro_input;
+
+/// Replaces every `[<segments>]` in the input with one identifier.
+///
+/// Segments are identifiers, `_`, integers and string literals, joined in
+/// order, so `[<try_ new>]` and `[<"open_" file>]` become `try_new` and
+/// `open_file`. Raw identifiers lose their `r#`. Everything else is passed
+/// through unchanged.
+#[proc_macro]
+pub fn join_idents(input: TokenStream) -> TokenStream {
+	join::expand(input.into())
+		.unwrap_or_else(syn::Error::into_compile_error)
+		.into()
+}
+
+/// Moves the tokens after `=>` to the location of the literal before it,
+/// leaving the contents of groups where they are.
+///
+/// A macro call moved this way expands at the literal, so errors in the code
+/// it generates point there, while errors in its arguments don't move.
+#[proc_macro]
+pub fn located_at(input: TokenStream) -> TokenStream {
+	located::expand(parse_macro_input!(input as located::Input)).into()
+}
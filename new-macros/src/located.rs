@@ -0,0 +1,41 @@
+use proc_macro2::{Span, TokenStream};
+use syn::{
+	parse::{Parse, ParseStream},
+	Lit, Result, Token,
+};
+
+/// `location => tokens`.
+pub struct Input {
+	location: Lit,
+	tokens: TokenStream,
+}
+
+impl Parse for Input {
+	fn parse(input: ParseStream<'_>) -> Result<Self> {
+		let location = input.parse()?;
+		input.parse::<Token![=>]>()?;
+
+		Ok(Self {
+			location,
+			tokens: input.parse()?,
+		})
+	}
+}
+
+pub fn expand(input: Input) -> TokenStream {
+	relocate(input.tokens, input.location.span())
+}
+
+/// Moves the tokens to `location`, keeping their hygiene.
+///
+/// Only the outer tokens move, along with the delimiters of groups, so a macro
+/// call expands at `location` while its arguments keep their own spans.
+fn relocate(tokens: TokenStream, location: Span) -> TokenStream {
+	tokens
+		.into_iter()
+		.map(|mut token| {
+			token.set_span(token.span().located_at(location));
+			token
+		})
+		.collect()
+}
@@ -0,0 +1,39 @@
+//! Tests for `join_idents!`.
+
+use new_macros::join_idents;
+
+struct Port(u16);
+
+impl Port {
+	const fn try_new(value: u16) -> Option<Self> {
+		if value == 0 {
+			None
+		} else {
+			Some(Self(value))
+		}
+	}
+
+	const fn open_2(value: u16) -> Self {
+		Self(value)
+	}
+}
+
+#[test]
+fn segments_are_joined() {
+	assert_eq!(
+		join_idents!(Port::[<try_ new>](8080)).map(|port| port.0),
+		Some(8080)
+	);
+	assert_eq!(join_idents!(Port::[<"open" _ 2>](80)).0, 80);
+	assert_eq!(
+		join_idents!(Port::[<r#try _new>](0)).map(|port| port.0),
+		None
+	);
+}
+
+#[test]
+fn other_tokens_are_kept() {
+	let values = join_idents!([1, 2, { [<Port>]::[<"open_" 2>](3).0 }]);
+
+	assert_eq!(values, [1, 2, 3]);
+}
@@ -0,0 +1,10 @@
+//! Tests for `located_at!`.
+
+use new_macros::located_at;
+
+#[test]
+fn tokens_are_kept() {
+	let value = located_at!("here" => [1, 2].iter().sum::<u8>());
+
+	assert_eq!(value, 3);
+}
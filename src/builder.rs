@@ -0,0 +1,34 @@
+use alloc::boxed::Box;
+use core::{error::Error, fmt};
+
+/// The error returned by `try_build` on builders from `#[derive(Builder)]`.
+#[derive(Debug)]
+pub enum BuildError {
+	/// A required field was never set, named like its setter.
+	Missing(&'static str),
+	/// A field was rejected by its validator.
+	Invalid {
+		/// The name of the field, like its setter.
+		field: &'static str,
+		/// The validator's error.
+		source: Box<dyn Error + Send + Sync>,
+	},
+}
+
+impl fmt::Display for BuildError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Missing(field) => write!(f, "missing required field `{field}`"),
+			Self::Invalid { field, .. } => write!(f, "invalid value for `{field}`"),
+		}
+	}
+}
+
+impl Error for BuildError {
+	fn source(&self) -> Option<&(dyn Error + 'static)> {
+		match self {
+			Self::Missing(_) => None,
+			Self::Invalid { source, .. } => Some(&**source),
+		}
+	}
+}
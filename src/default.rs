@@ -0,0 +1,26 @@
+use core::convert::Infallible;
+
+/// Types with a fallible default value, used by [`try_default!`].
+///
+/// Every [`Default`] type implements this and never fails.
+///
+/// [`try_default!`]: crate::try_default
+pub trait TryDefault: Sized {
+	/// The error returned when no default can be made.
+	type Error;
+
+	/// Returns the default value for the type.
+	///
+	/// # Errors
+	///
+	/// Returns an error when no default can be made.
+	fn try_default() -> Result<Self, Self::Error>;
+}
+
+impl<T: Default> TryDefault for T {
+	type Error = Infallible;
+
+	fn try_default() -> Result<Self, Self::Error> {
+		Ok(Self::default())
+	}
+}
@@ -0,0 +1,224 @@
+#[cfg(feature = "alloc")]
+use alloc::{boxed::Box, vec::Vec};
+use core::{marker::PhantomData, mem::MaybeUninit, ptr};
+
+/// An initializer that writes a `T` straight into its final location.
+///
+/// [`init!`] and [`init_array`] build initializers that write one field or
+/// element at a time, so large values never live on the stack.
+///
+/// # Safety
+///
+/// When [`init`](Init::init) returns, `slot` must hold a valid `T`. When it
+/// unwinds, everything it initialized must have been dropped again, leaving
+/// nothing in `slot` that needs dropping.
+///
+/// [`init!`]: crate::init
+pub unsafe trait Init<T> {
+	/// Initializes the value at `slot`.
+	///
+	/// # Safety
+	///
+	/// `slot` must be valid for writes and properly aligned. Whatever it held
+	/// before is overwritten without being dropped.
+	unsafe fn init(self, slot: *mut T);
+}
+
+/// Proof that an [`Initializer`]'s closure initialized its slot.
+///
+/// Returning one is the only way for the closure to finish, so it can't
+/// `return` early with fields left uninitialized.
+pub struct Initialized {
+	_private: (),
+}
+
+impl Initialized {
+	/// Creates the proof.
+	///
+	/// # Safety
+	///
+	/// The slot the closure was given must be fully initialized.
+	#[must_use]
+	pub const unsafe fn new() -> Self {
+		Self { _private: () }
+	}
+}
+
+/// An [`Init`] implemented by a closure.
+pub struct Initializer<T, F> {
+	init: F,
+	marker: PhantomData<fn(*mut T)>,
+}
+
+impl<T, F: FnOnce(*mut T) -> Initialized> Initializer<T, F> {
+	/// Wraps a closure that initializes the `T` behind the pointer it's given,
+	/// returning [`Initialized`] once it has.
+	///
+	/// # Safety
+	///
+	/// `init` must uphold the contract of [`Init`]: it must fully initialize
+	/// the slot when it returns, and drop anything it initialized when it
+	/// unwinds.
+	pub const unsafe fn new(init: F) -> Self {
+		Self {
+			init,
+			marker: PhantomData,
+		}
+	}
+}
+
+// SAFETY: `Initializer::new` requires the closure to uphold the contract.
+unsafe impl<T, F: FnOnce(*mut T) -> Initialized> Init<T> for Initializer<T, F> {
+	#[inline]
+	unsafe fn init(self, slot: *mut T) {
+		(self.init)(slot);
+	}
+}
+
+/// Initializes an array in place, one element at a time.
+///
+/// `init(i)` returns the element at index `i`, which is moved into place
+/// right away. If it unwinds, the elements initialized so far are dropped.
+pub fn init_array<T, F, const N: usize>(
+	mut init: F,
+) -> Initializer<[T; N], impl FnOnce(*mut [T; N]) -> Initialized>
+where
+	F: FnMut(usize) -> T,
+{
+	let init = move |slot: *mut [T; N]| {
+		let mut guard = ArrayGuard {
+			first: slot.cast::<T>(),
+			len: 0,
+		};
+
+		while guard.len < N {
+			let element = init(guard.len);
+			// SAFETY: The index is in bounds of the array at `slot`.
+			unsafe { guard.first.add(guard.len).write(element) };
+			guard.len += 1;
+		}
+
+		core::mem::forget(guard);
+
+		// SAFETY: Every element was initialized above.
+		unsafe { Initialized::new() }
+	};
+
+	// SAFETY: Every element is initialized on return, and the guard drops the
+	// initialized ones when unwinding.
+	unsafe { Initializer::new(init) }
+}
+
+/// Drops the first `len` elements at `first` unless forgotten.
+struct ArrayGuard<T> {
+	first: *mut T,
+	len: usize,
+}
+
+impl<T> Drop for ArrayGuard<T> {
+	fn drop(&mut self) {
+		// SAFETY: The first `len` elements were initialized.
+		unsafe { ptr::drop_in_place(ptr::slice_from_raw_parts_mut(self.first, self.len)) };
+	}
+}
+
+/// Uninitialized memory an [`Init`] can write a `T` into, used by
+/// [`emplace!`].
+///
+/// [`emplace!`]: crate::emplace
+pub trait Place<T> {
+	/// The initialized place.
+	type Output;
+
+	/// Initializes the place with `init`.
+	fn emplace<I: Init<T>>(self, init: I) -> Self::Output;
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Place<T> for Box<MaybeUninit<T>> {
+	type Output = Box<T>;
+
+	#[inline]
+	fn emplace<I: Init<T>>(mut self, init: I) -> Self::Output {
+		// SAFETY: The box is valid for writes, and a panic leaves it
+		// uninitialized, which `MaybeUninit` is fine with.
+		unsafe {
+			init.init(self.as_mut_ptr());
+			self.assume_init()
+		}
+	}
+}
+
+impl<'a, T> Place<T> for &'a mut MaybeUninit<T> {
+	type Output = &'a mut T;
+
+	#[inline]
+	fn emplace<I: Init<T>>(self, init: I) -> Self::Output {
+		// SAFETY: As for `Box<MaybeUninit<T>>`.
+		unsafe {
+			init.init(self.as_mut_ptr());
+			self.assume_init_mut()
+		}
+	}
+}
+
+/// Pushes the value, returning a reference to it.
+#[cfg(feature = "alloc")]
+impl<'a, T> Place<T> for &'a mut Vec<T> {
+	type Output = &'a mut T;
+
+	#[inline]
+	fn emplace<I: Init<T>>(self, init: I) -> Self::Output {
+		let len = self.len();
+		self.reserve(1);
+
+		// SAFETY: The spare capacity is valid for writes, and the length only
+		// grows once the value is initialized.
+		unsafe {
+			init.init(self.as_mut_ptr().add(len));
+			self.set_len(len + 1);
+		}
+
+		&mut self[len]
+	}
+}
+
+/// Moves an already built value into place, used by [`init!`] for `new!`
+/// forms.
+///
+/// [`init!`]: crate::init
+#[doc(hidden)]
+pub fn value<T>(value: T) -> Initializer<T, impl FnOnce(*mut T) -> Initialized> {
+	// SAFETY: The write fully initializes the slot and can't unwind.
+	unsafe {
+		Initializer::new(move |slot: *mut T| {
+			slot.write(value);
+			Initialized::new()
+		})
+	}
+}
+
+/// Drops the value at a pointer unless forgotten, used by [`init!`] to drop
+/// the initialized fields when a later one unwinds.
+///
+/// [`init!`]: crate::init
+#[doc(hidden)]
+pub struct DropGuard<T>(*mut T);
+
+impl<T> DropGuard<T> {
+	/// # Safety
+	///
+	/// `value` must be initialized and not dropped elsewhere while the guard
+	/// lives.
+	#[doc(hidden)]
+	pub const unsafe fn new(value: *mut T) -> Self {
+		Self(value)
+	}
+}
+
+impl<T> Drop for DropGuard<T> {
+	fn drop(&mut self) {
+		// SAFETY: `DropGuard::new` requires the value to be initialized.
+		unsafe { ptr::drop_in_place(self.0) };
+	}
+}
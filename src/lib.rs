@@ -1,82 +1,828 @@
 //! A helper macro for creating structs with `new`.
+//!
+//! With the `derive` feature, [`New`], [`TryNew`], [`With`], [`Builder`] and
+//! [`FromStr`] generate the constructors these macros call.
+//!
+//! The crate is `no_std`. The `alloc` feature adds everything that needs the
+//! heap, like the smart-pointer forms, [`Pool`] and [`BuildError`], and the
+//! `std` feature, enabled by default, adds the `Mutex` and `RwLock` wrappers.
+
+#![no_std]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
+#[cfg(feature = "alloc")]
+mod builder;
+mod default;
+mod emplace;
+mod named;
+mod parse;
+mod pinned;
+#[cfg(feature = "alloc")]
+mod pool;
+mod traits;
+mod wrap;
+
+#[cfg(feature = "derive")]
+pub use new_derive::{Builder, FromStr, New, TryNew, With};
+
+#[cfg(feature = "alloc")]
+pub use self::{
+	builder::BuildError,
+	pool::{Pool, PoolStats, Pooled},
+};
+pub use self::{
+	default::TryDefault,
+	emplace::{init_array, Init, Initialized, Initializer, Place},
+	named::{NamedArgs, NamedNew},
+	parse::ParseVariantError,
+	traits::{New, TryNew},
+	wrap::Wrap,
+};
+
+/// The `allocator-api2` crate, for allocators to pass to [`new_in!`] on
+/// stable Rust.
+#[cfg(feature = "allocator-api2")]
+pub use allocator_api2;
+
+#[doc(hidden)]
+pub mod __private {
+	/// Lets any type, generics included, name a struct expression.
+	pub type Type<T> = T;
+
+	#[cfg(all(feature = "alloc", target_has_atomic = "ptr"))]
+	pub use alloc::sync::Arc;
+	#[cfg(feature = "alloc")]
+	pub use alloc::{boxed::Box, rc::Rc};
+
+	pub use new_macros::{join_idents, located_at};
+
+	#[cfg(feature = "alloc")]
+	pub use crate::pinned::boxed;
+	pub use crate::{
+		emplace::{value, DropGuard},
+		pinned::StackSlot,
+	};
+
+	/// Stands in for field values in checks that are never run.
+	#[must_use]
+	pub const fn unreachable<T>() -> T {
+		::core::unreachable!()
+	}
+}
 
 #[doc(hidden)]
 #[macro_export]
 macro_rules! __internal_new {
-    ($struct:tt$(<$($gen:tt),*>)?, $constructor:ident $($args:tt),*) => {
-        <$struct$(<$($gen)*,>)?>::$constructor($($args,)*)
-    };
+	// Entry point, `[default constructor (in trait)] [name prefix] [name suffix]
+	// ({[leading args] [trailing args]}) input`, where the extra arguments are
+	// passed to the constructor around the ones in `input`.
+	([$($default:tt)*] [$($prefix:tt)*] [$($suffix:tt)*] {[$($lead:tt)*] [$($trail:tt)*]} $($input:tt)+) => {
+		$crate::__internal_new!(@munch [$($default)*] [$($prefix)*] [$($suffix)*] {[$($lead)*] [$($trail)*]} [] $($input)+)
+	};
+	([$($default:tt)*] [$($prefix:tt)*] [$($suffix:tt)*] $($input:tt)+) => {
+		$crate::__internal_new!(@munch [$($default)*] [$($prefix)*] [$($suffix)*] {[] []} [] $($input)+)
+	};
+	(@munch [new] [] [] {[] []} [$($struct:tt)+] {$($fields:tt)*}) => {
+		$crate::__internal_new!(@named {$($struct)+} {$($fields)*})
+	};
+	(@munch [reinit] $prefix:tt $suffix:tt $extra:tt [$($struct:tt)+] {$($fields:tt)*}) => {
+		::core::compile_error!("named arguments aren't supported by `new!(in pool => ...)`, e.g. use `new!(in pool => Type(args))`")
+	};
+	(@munch $default:tt $prefix:tt $suffix:tt $extra:tt [$($struct:tt)+] {$($fields:tt)*}) => {
+		::core::compile_error!("named arguments are only supported by `new!`")
+	};
+	(@munch [] $prefix:tt $suffix:tt $extra:tt [$($struct:tt)+] ($($args:tt)*)) => {
+		::core::compile_error!("a constructor name is required, e.g. `Type: name(args)`")
+	};
+	(@munch [$default:ident] $prefix:tt $suffix:tt $extra:tt [$($struct:tt)+] ($($args:tt)*)) => {
+		$crate::__internal_new!(@call {$($struct)+} $default $extra ($($args)*))
+	};
+	(@munch [$default:ident in $($trait:tt)+] $prefix:tt $suffix:tt $extra:tt [$($struct:tt)+] ($($args:tt)*)) => {{
+		use $($trait)+ as _;
+		$crate::__internal_new!(@call {$($struct)+} $default $extra ($($args)*))
+	}};
+	(@munch $default:tt [$($prefix:tt)*] [$($suffix:tt)*] $extra:tt [$($struct:tt)+] : $constructor:ident ($($args:tt)*)) => {
+		$crate::__private::join_idents! {
+			$crate::__internal_new!(@call {$($struct)+} [<$($prefix)* $constructor $($suffix)*>] $extra ($($args)*))
+		}
+	};
+	// Constructor generics, `Type: name::<G>(args)` or `Type: ::<G>(args)` for
+	// the default constructor.
+	(@munch $default:tt $prefix:tt $suffix:tt $extra:tt [$($struct:tt)+] : $constructor:ident :: < $($rest:tt)+) => {
+		$crate::__internal_new!(@turbofish $default $prefix $suffix $extra [$($struct)+] {$constructor} [:: <] $($rest)+)
+	};
+	(@munch $default:tt $prefix:tt $suffix:tt $extra:tt [$($struct:tt)+] : :: < $($rest:tt)+) => {
+		$crate::__internal_new!(@turbofish $default $prefix $suffix $extra [$($struct)+] {} [:: <] $($rest)+)
+	};
+	// `Wrapper<_> <- inner`, where `inner` is either a macro call or a `new!`
+	// form. `<-` only starts one after `_>`, since `Neg<-1>` is a valid type.
+	(@munch $default:tt $prefix:tt $suffix:tt $extra:tt [$($struct:tt)+] _ > $($rest:tt)+) => {
+		$crate::__internal_new!(@closing $default $prefix $suffix $extra [$($struct)+ _ >] $($rest)+)
+	};
+	(@munch $default:tt $prefix:tt $suffix:tt $extra:tt [$($struct:tt)+] _ >> $($rest:tt)+) => {
+		$crate::__internal_new!(@closing $default $prefix $suffix $extra [$($struct)+ _ >>] $($rest)+)
+	};
+	(@munch [new] [] [] {[] []} [$($struct:tt)+] as New ($($args:tt)*)) => {
+		$crate::__internal_new!(@trait {$($struct)+} New new ($($args)*))
+	};
+	(@munch [try_new] [try_] [] {[] []} [$($struct:tt)+] as TryNew ($($args:tt)*)) => {
+		$crate::__internal_new!(@trait {$($struct)+} TryNew try_new ($($args)*))
+	};
+	(@munch [reinit] $prefix:tt $suffix:tt $extra:tt [$($struct:tt)+] as $trait:ident ($($args:tt)*)) => {
+		::core::compile_error!("trait forms aren't supported by `new!(in pool => ...)`")
+	};
+	(@munch $default:tt $prefix:tt $suffix:tt $extra:tt [$($struct:tt)+] as $trait:ident ($($args:tt)*)) => {
+		::core::compile_error!("trait forms are `new!(Type as New(args))` and `try_new!(Type as TryNew(args))`")
+	};
+	(@munch $default:tt $prefix:tt $suffix:tt $extra:tt [$($struct:tt)*] $next:tt $($rest:tt)*) => {
+		$crate::__internal_new!(@munch $default $prefix $suffix $extra [$($struct)* $next] $($rest)*)
+	};
+	// The rest of the closing brackets after `_`, then either `<- inner` or
+	// back to munching.
+	(@closing $default:tt $prefix:tt $suffix:tt $extra:tt [$($struct:tt)+] > $($rest:tt)+) => {
+		$crate::__internal_new!(@closing $default $prefix $suffix $extra [$($struct)+ >] $($rest)+)
+	};
+	(@closing $default:tt $prefix:tt $suffix:tt $extra:tt [$($struct:tt)+] >> $($rest:tt)+) => {
+		$crate::__internal_new!(@closing $default $prefix $suffix $extra [$($struct)+ >>] $($rest)+)
+	};
+	(@closing [new] [] [] {[] []} [$($wrapper:tt)+] <- $($macro:ident)::+ ! $($inner:tt)+) => {
+		$crate::__internal_new!(@init_call [$($macro)::+] {$($macro)::+ ! $($inner)+} $($wrapper)+)
+	};
+	(@closing [new] [] [] {[] []} [$($wrapper:tt)+] <- $($inner:tt)+) => {
+		$crate::__internal_new!(@wrap {layers $crate::new!($($inner)+)} [] [] $($wrapper)+)
+	};
+	(@closing [reinit] $prefix:tt $suffix:tt $extra:tt [$($wrapper:tt)+] <- $($inner:tt)+) => {
+		::core::compile_error!("wrapper forms aren't supported by `new!(in pool => ...)`, which already boxes the value")
+	};
+	(@closing $default:tt $prefix:tt $suffix:tt $extra:tt [$($wrapper:tt)+] <- $($inner:tt)+) => {
+		::core::compile_error!("wrapper forms are only supported by `new!`, e.g. `new!(Box<_> <- try_new!(Type(args))?)`")
+	};
+	(@closing $default:tt $prefix:tt $suffix:tt $extra:tt [$($struct:tt)+] $($rest:tt)+) => {
+		$crate::__internal_new!(@munch $default $prefix $suffix $extra [$($struct)+] $($rest)+)
+	};
+	// Splits the generics from the arguments, the last token.
+	(@turbofish [] $prefix:tt $suffix:tt $extra:tt $struct:tt {} $generics:tt ($($args:tt)*)) => {
+		::core::compile_error!("a constructor name is required, e.g. `Type: name::<G>(args)`")
+	};
+	(@turbofish [$default:ident] $prefix:tt $suffix:tt $extra:tt [$($struct:tt)+] {} $generics:tt ($($args:tt)*)) => {
+		$crate::__internal_new!(@call {$($struct)+} $default $generics $extra ($($args)*))
+	};
+	(@turbofish [$default:ident in $($trait:tt)+] $prefix:tt $suffix:tt $extra:tt [$($struct:tt)+] {} $generics:tt ($($args:tt)*)) => {{
+		use $($trait)+ as _;
+		$crate::__internal_new!(@call {$($struct)+} $default $generics $extra ($($args)*))
+	}};
+	(@turbofish $default:tt [$($prefix:tt)*] [$($suffix:tt)*] $extra:tt [$($struct:tt)+] {$constructor:ident} $generics:tt ($($args:tt)*)) => {
+		$crate::__private::join_idents! {
+			$crate::__internal_new!(@call {$($struct)+} [<$($prefix)* $constructor $($suffix)*>] $generics $extra ($($args)*))
+		}
+	};
+	(@turbofish $default:tt $prefix:tt $suffix:tt $extra:tt $struct:tt $constructor:tt [$($generics:tt)*] $next:tt $($rest:tt)+) => {
+		$crate::__internal_new!(@turbofish $default $prefix $suffix $extra $struct $constructor [$($generics)* $next] $($rest)+)
+	};
+	// Qualified paths like `<T as Trait>` are already valid expression paths.
+	(@call {< $($qualified:tt)+} $constructor:ident $([$($generics:tt)*])? {[$($lead:expr),*] [$($trail:expr),*]} ($($args:expr),* $(,)?)) => {
+		<$($qualified)+::$constructor $($($generics)*)? ($($lead,)* $($args,)* $($trail),*)
+	};
+	(@call {<< $($qualified:tt)+} $constructor:ident $([$($generics:tt)*])? {[$($lead:expr),*] [$($trail:expr),*]} ($($args:expr),* $(,)?)) => {
+		<<$($qualified)+::$constructor $($($generics)*)? ($($lead,)* $($args,)* $($trail),*)
+	};
+	(@call {$struct:ty} $constructor:ident $([$($generics:tt)*])? {[$($lead:expr),*] [$($trail:expr),*]} ($($args:expr),* $(,)?)) => {
+		<$struct>::$constructor $($($generics)*)? ($($lead,)* $($args,)* $($trail),*)
+	};
+	// An `init!` call is initialized straight into the innermost layer, other
+	// macro calls are wrapped like values.
+	(@init_call [init] {$($call:tt)+} $($wrapper:tt)+) => {
+		$crate::__internal_new!(@wrap {init_layers $($call)+} [] [] $($wrapper)+)
+	};
+	(@init_call [$first:ident :: $($rest:tt)+] $call:tt $($wrapper:tt)+) => {
+		$crate::__internal_new!(@init_call [$($rest)+] $call $($wrapper)+)
+	};
+	(@init_call [$other:ident] {$($call:tt)+} $($wrapper:tt)+) => {
+		$crate::__internal_new!(@wrap {layers $($call)+} [] [] $($wrapper)+)
+	};
+	// Splits `A<B<_>>` into the layers `{A} {B}`, innermost last.
+	(@wrap $inner:tt [$($layers:tt)*] [] :: $($rest:tt)+) => {
+		$crate::__internal_new!(@wrap $inner [$($layers)*] [::] $($rest)+)
+	};
+	(@wrap $inner:tt [$($layers:tt)*] [$($path:tt)*] $segment:ident :: $($rest:tt)+) => {
+		$crate::__internal_new!(@wrap $inner [$($layers)*] [$($path)* $segment ::] $($rest)+)
+	};
+	(@wrap $inner:tt [$($layers:tt)*] [$($path:tt)*] $segment:ident < $($rest:tt)+) => {
+		$crate::__internal_new!(@wrap $inner [$($layers)* {$($path)* $segment}] [] $($rest)+)
+	};
+	(@wrap {$mode:ident $($inner:tt)+} [$($layers:tt)+] [] _ $($closing:tt)+) => {
+		$crate::__internal_new!(@ $mode [$($layers)+] $($inner)+)
+	};
+	(@wrap $inner:tt $layers:tt $path:tt $($rest:tt)*) => {
+		::core::compile_error!("wrappers are written as `Wrapper<_>`, or nested like `Arc<Mutex<_>>`")
+	};
+	(@layers [] $inner:expr) => {
+		$inner
+	};
+	(@layers [{$($layer:tt)+} $($rest:tt)*] $inner:expr) => {
+		<$($layer)+<_> as $crate::Wrap<_>>::wrap($crate::__internal_new!(@layers [$($rest)*] $inner))
+	};
+	(@init_layers [{$($layer:tt)+}] $init:expr) => {
+		<$($layer)+<_> as $crate::Wrap<_>>::wrap_init($init)
+	};
+	(@init_layers [{$($layer:tt)+} $($rest:tt)+] $init:expr) => {
+		<$($layer)+<_> as $crate::Wrap<_>>::wrap($crate::__internal_new!(@init_layers [$($rest)+] $init))
+	};
+	(@trait {$struct:ty} $trait:ident $method:ident ($($args:expr),* $(,)?)) => {
+		<$struct as $crate::$trait<_>>::$method(($($args,)*))
+	};
+	(@named {$struct:ty} {$($fields:tt)*}) => {
+		<$struct as $crate::NamedNew>::new_named($crate::NamedArgs::<$struct> { $($fields)* })
+	};
 }
 
 /// A helper for creating structs akin to the `new` keyword in other languages.
+///
+/// The type may be any path, including `::`-prefixed, `crate::`/`super::`
+/// paths and qualified `<T as Trait>` types, with generic arguments of any
+/// shape (`HashMap<String, Vec<u8>>`, `Cow<'_, str>`, `Fixed<4>`, `Vec<_>`).
+/// Generic arguments for the constructor itself follow its name, as in
+/// `new!(Type: from_parts::<u32, _>(a, b))`, or stand in for it with the
+/// default constructor, as in `new!(Type: ::<u32>(a))`.
+///
+/// `new!(Type { name: value })` passes arguments by name instead, in any
+/// order, for types implementing [`NamedNew`].
+///
+/// `new!(Wrapper<_> <- form)` builds the value with `form` and wraps it in
+/// each layer of `Wrapper`, outermost last, through [`Wrap`]. `form` is any
+/// `new!` form or another macro call, like `new!(Arc<Mutex<_>> <- Type(args))`
+/// or `new!(Box<_> <- try_new!(Type(args))?)`. With an [`init!`] call, like
+/// `new!(Box<_> <- init!(Type { field: value }))`, the value is initialized
+/// straight into a `Box`, `Rc` or `Arc` allocation instead of on the stack.
+/// [`boxed!`], [`rc!`], [`arc!`] and [`pin!`] are shortcuts for the common
+/// pointers.
+///
+/// `new!(in pool => form)` checks a value out of a [`Pool`], reinitializing
+/// a recycled slot with `reinit`/`reinit_<name>` when one is free. Only the
+/// positional `Type(args)` and `Type: name(args)` forms are supported.
+///
+/// `new!(Type as New(args))` calls the constructor through the [`New`]
+/// trait, which works for generic `Type`s bound on it.
+///
+/// Every form that calls a `const fn` expands to a plain call, so `new!`,
+/// [`try_new!`], [`with!`], and `from!`/`try_from!` with a named constructor
+/// work in `const` and `static` items. Named arguments and the
+/// `from!(Type(value))` shorthands call trait methods, which aren't `const`.
+/// Use [`const_new!`] to require const evaluation.
 #[macro_export]
 macro_rules! new {
-    ($struct:tt$(<$($gen:tt),*>)?($($args:tt),*)) => {
-        $crate::__internal_new!($struct$(<$($gen),*>)?, new $($args),*)
-    };
-    ($struct:tt$(<$($gen:tt),*>)?: $constructor:tt($($args:tt),*)) => {
-        $crate::__internal_new!($struct$(<$($gen),*>)?, $constructor $($args),*)
-    };
+	(in $pool:expr => $($input:tt)+) => {{
+		let pool = &$pool;
+		match $crate::Pool::recycle(pool) {
+			::core::option::Option::Some(mut value) => {
+				$crate::__internal_new!([reinit] [reinit_] [] {[&mut *value] []} $($input)+);
+				$crate::Pool::check_out(pool, value)
+			}
+			::core::option::Option::None => {
+				$crate::Pool::check_out(pool, $crate::__private::Box::new($crate::new!($($input)+)))
+			}
+		}
+	}};
+	($($input:tt)+) => {
+		$crate::__internal_new!([new] [] [] $($input)+)
+	};
+}
+
+/// Builds a value with any [`new!`] form or macro call and boxes it.
+///
+/// Short for `new!(Box<_> <- form)`.
+#[cfg(feature = "alloc")]
+#[macro_export]
+macro_rules! boxed {
+	($($input:tt)+) => {
+		$crate::new!($crate::__private::Box<_> <- $($input)+)
+	};
+}
+
+/// Builds a value with any [`new!`] form or macro call and puts it in an
+/// [`Rc`](alloc::rc::Rc).
+///
+/// Short for `new!(Rc<_> <- form)`.
+#[cfg(feature = "alloc")]
+#[macro_export]
+macro_rules! rc {
+	($($input:tt)+) => {
+		$crate::new!($crate::__private::Rc<_> <- $($input)+)
+	};
+}
+
+/// Builds a value with any [`new!`] form or macro call and puts it in an
+/// [`Arc`](alloc::sync::Arc).
+///
+/// Short for `new!(Arc<_> <- form)`.
+#[cfg(all(feature = "alloc", target_has_atomic = "ptr"))]
+#[macro_export]
+macro_rules! arc {
+	($($input:tt)+) => {
+		$crate::new!($crate::__private::Arc<_> <- $($input)+)
+	};
+}
+
+/// Builds a value with any [`new!`] form or macro call and pins it in a
+/// [`Box`].
+///
+/// Short for `new!(Pin<Box<_>> <- form)`. Unlike [`core::pin::pin!`], the
+/// value lives on the heap, so the result can be returned.
+#[cfg(feature = "alloc")]
+#[macro_export]
+macro_rules! pin {
+	($($input:tt)+) => {
+		$crate::new!(::core::pin::Pin<$crate::__private::Box<_>> <- $($input)+)
+	};
+}
+
+/// Like [`new!`], but evaluated at compile time.
+///
+/// Expands to an inline `const` block, so calling a constructor that isn't a
+/// `const fn`, or passing arguments that aren't constants, fails to compile.
+#[macro_export]
+macro_rules! const_new {
+	($($input:tt)+) => {
+		const { $crate::new!($($input)+) }
+	};
 }
 
 /// A shortcut for calling `try_*` constructors for structs.
+///
+/// Both `try_new!(Type(args))` and `try_new!(Type: name(args))` accept the
+/// same types as [`new!`], generics included. `try_new!(Type as TryNew(args))`
+/// goes through the [`TryNew`] trait instead.
 #[macro_export]
 macro_rules! try_new {
-    ($struct:tt($($args:tt),*)) => {
-        $crate::__internal_new!($struct, try_new $($args),*)
-    };
-    ($struct:tt$(<$($gen:tt),*>)?: $constructor:tt($($args:tt),*)) => {
-        ::paste::paste! {
-            $crate::__internal_new!($struct$(<$($gen),*>)?, [<try_ $constructor>] $($args),*)
-        }
-    };
+	($($input:tt)+) => {
+		$crate::__internal_new!([try_new] [try_] [] $($input)+)
+	};
 }
 
 /// A shortcut for calling `with_*` constructors for structs.
 #[macro_export]
 macro_rules! with {
-    ($struct:tt$(<$($gen:tt),*>)?: $constructor:tt($($args:tt),*)) => {
-        ::paste::paste! {
-            $crate::__internal_new!($struct$(<$($gen),*>)?, [<with_ $constructor>] $($args),*)
-        }
-    }
+	($($input:tt)+) => {
+		$crate::__internal_new!([] [with_] [] $($input)+)
+	};
+}
+
+/// An async [`new!`], awaiting `new_async` or `<name>_async` constructors.
+///
+/// `new_async!(Type(args))` awaits `Type::new_async(args)` and
+/// `new_async!(Type: name(args))` awaits `Type::name_async(args)`, accepting
+/// the same types as [`new!`].
+#[macro_export]
+macro_rules! new_async {
+	($($input:tt)+) => {
+		$crate::__internal_new!([new_async] [] [_async] $($input)+).await
+	};
+}
+
+/// An async [`try_new!`], awaiting `try_new_async` or `try_<name>_async`
+/// constructors.
+#[macro_export]
+macro_rules! try_new_async {
+	($($input:tt)+) => {
+		$crate::__internal_new!([try_new_async] [try_] [_async] $($input)+).await
+	};
+}
+
+/// An async [`with!`], awaiting `with_<name>_async` constructors.
+#[macro_export]
+macro_rules! with_async {
+	($($input:tt)+) => {
+		$crate::__internal_new!([] [with_] [_async] $($input)+).await
+	};
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __internal_new_in {
+	// Splits `form in allocator` and passes the allocator as the last argument.
+	($default:tt $prefix:tt [$($form:tt)+] in $alloc:expr) => {
+		$crate::__internal_new!($default $prefix [_in] {[] [$alloc]} $($form)+)
+	};
+	($default:tt $prefix:tt [$($form:tt)*]) => {
+		::core::compile_error!("an allocator is required, e.g. `new_in!(Type(args) in alloc)`")
+	};
+	($default:tt $prefix:tt [$($form:tt)*] $next:tt $($rest:tt)*) => {
+		$crate::__internal_new_in!($default $prefix [$($form)* $next] $($rest)*)
+	};
+}
+
+/// A shortcut for calling allocator-aware `new_in`/`<name>_in` constructors.
+///
+/// `new_in!(Type(args) in alloc)` calls `Type::new_in(args, alloc)` and
+/// `new_in!(Type: name(args) in alloc)` calls `Type::name_in(args, alloc)`,
+/// following the standard library's `Vec::with_capacity_in` convention of
+/// taking the allocator last. Types accept the same paths and generics as
+/// [`new!`].
+///
+/// The standard collections only take allocators on nightly, but the
+/// `allocator-api2` feature re-exports [`allocator_api2`](crate::allocator_api2)
+/// whose collections work on stable, with arenas or any other allocator.
+#[macro_export]
+macro_rules! new_in {
+	($($input:tt)+) => {
+		$crate::__internal_new_in!([new_in] [] [] $($input)+)
+	};
+}
+
+/// A shortcut for calling allocator-aware `try_new_in`/`try_<name>_in`
+/// constructors.
+///
+/// Mirrors [`new_in!`] with the naming of [`try_new!`].
+#[macro_export]
+macro_rules! try_new_in {
+	($($input:tt)+) => {
+		$crate::__internal_new_in!([try_new_in] [try_] [] $($input)+)
+	};
 }
 
 /// A shortcut for calling `from_*`/[`from`] for structs.
 ///
-/// [`from`]: std::convert::From::from
+/// Both `from!(Type(value))` and `from!(Type: name(args))` accept the same
+/// types as [`new!`], generics included.
+///
+/// [`from`]: core::convert::From::from
 #[macro_export]
 macro_rules! from {
-	($struct:tt($($args:tt),*)) => {{
-		use ::std::convert::From as _;
-        $crate::__internal_new!($struct, from $($args),*)
-	}};
-    ($struct:tt$(<$($gen:tt),*>)?: $constructor:tt($($args:tt),*)) => {
-        ::paste::paste! {
-            $crate::__internal_new!($struct$(<$($gen),*>)?, [<from_ $constructor>] $($args),*)
-        }
-    }
+	($($input:tt)+) => {
+		$crate::__internal_new!([from in ::core::convert::From] [from_] [] $($input)+)
+	};
 }
 
 /// A shortcut for calling `try_from_*`/[`try_from`] for structs.
 ///
-/// [`try_from`]: std::convert::TryFrom::try_from
+/// Both `try_from!(Type(value))` and `try_from!(Type: name(args))` accept the
+/// same types as [`new!`], generics included.
+///
+/// [`try_from`]: core::convert::TryFrom::try_from
 #[macro_export]
 macro_rules! try_from {
-    ($struct:tt($($args:tt),*)) => {{
-        use ::std::convert::TryFrom as _;
-        $crate::__internal_new!($struct, try_from $($args),*)
-    }};
-    ($struct:tt$(<$($gen:tt),*>)?: $constructor:tt($($args:tt),*)) => {
-        ::paste::paste! {
-            $crate::__internal_new!($struct$(<$($gen),*>)?, [<try_from_ $constructor>] $($args),*)
-        }
-    }
-}
-
-#[cfg(test)]
+	($($input:tt)+) => {
+		$crate::__internal_new!([try_from in ::core::convert::TryFrom] [try_from_] [] $($input)+)
+	};
+}
+
+/// A shortcut for calling unsafe `new_unchecked`/`<name>_unchecked`
+/// constructors.
+///
+/// `unchecked!(Type(args))` calls `Type::new_unchecked(args)` and
+/// `unchecked!(Type: name(args))` calls `Type::name_unchecked(args)`,
+/// accepting the same types as [`new!`]. The call is not wrapped in `unsafe`,
+/// so it has to be made from an `unsafe` block.
+///
+/// A leading `SAFETY: "reason",` records why the call is sound. A missing
+/// `unsafe` block is then reported at the reason, while errors in the
+/// arguments are still reported at the arguments.
+#[macro_export]
+macro_rules! unchecked {
+	(SAFETY: $reason:literal, $($input:tt)+) => {
+		$crate::__private::located_at!($reason => $crate::unchecked!($($input)+))
+	};
+	($($input:tt)+) => {
+		$crate::__internal_new!([new_unchecked] [] [_unchecked] $($input)+)
+	};
+}
+
+/// Defines a constructor macro like [`with!`] for another naming convention.
+///
+/// `define_constructor_macro!(open, prefix = "open_")` defines `open!`, so
+/// `open!(Type: file(args))` calls `Type::open_file(args)` with the same
+/// generic and path handling as [`with!`]. The options, all optional but in
+/// this order, are:
+///
+/// - `prefix = "..."` and `suffix = "..."`, joined around the name.
+/// - `default = name`, the constructor `open!(Type(args))` calls. Without it
+///   a name is required, like for [`with!`].
+/// - `fallible = try_name`, which also defines a `try_name!` macro for the
+///   `try_`-prefixed constructors, calling `try_<default>` by default.
+///
+/// Attributes before the name, like `#[macro_export]`, are applied to every
+/// defined macro.
+#[macro_export]
+macro_rules! define_constructor_macro {
+	(
+		$(#[$attr:meta])* $name:ident
+		$(, prefix = $prefix:literal)? $(, suffix = $suffix:literal)?
+		$(, default = $default:ident)? $(, fallible = $fallible:ident)? $(,)?
+	) => {
+		$crate::__internal_define_constructor_macro! {
+			($) [$(#[$attr])*] $name [$($default)?] [$($prefix)?] [$($suffix)?] [$($fallible)?]
+		}
+	};
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __internal_define_constructor_macro {
+	// `$d` is a `$` passed in from outside, since the defined macros need
+	// their own metavariables.
+	(($d:tt) [$($attr:tt)*] $name:ident [$($default:ident)?] $prefix:tt $suffix:tt []) => {
+		$($attr)*
+		macro_rules! $name {
+			($d($d input:tt)+) => {
+				$crate::__internal_new!([$($default)?] $prefix $suffix $d($d input)+)
+			};
+		}
+	};
+	(($d:tt) $attr:tt $name:ident [$($default:ident)?] [$($prefix:tt)*] $suffix:tt [$fallible:ident]) => {
+		$crate::__internal_define_constructor_macro! {
+			($d) $attr $name [$($default)?] [$($prefix)*] $suffix []
+		}
+		$crate::__private::join_idents! {
+			$crate::__internal_define_constructor_macro! {
+				($d) $attr $fallible [$([<try_ $default>])?] [try_ $($prefix)*] $suffix []
+			}
+		}
+	};
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __internal_braced {
+	// Splits `Type { fields }` and calls back into `$callback!($($args)* {Type} {fields})`.
+	($callback:ident!($($args:tt)*) [$($struct:tt)+] {$($fields:tt)*}) => {
+		$crate::$callback!($($args)* {$($struct)+} {$($fields)*})
+	};
+	($callback:ident!($($args:tt)*) [$($struct:tt)*] $next:tt $($rest:tt)*) => {
+		$crate::__internal_braced!($callback!($($args)*) [$($struct)* $next] $($rest)*)
+	};
+}
+
+/// A shortcut for filling in builders from `#[derive(Builder)]`.
+///
+/// `builder!(Type { field: value })` calls `build()` once every field is set,
+/// while `builder!(Type { field: value, .. })` returns the builder so more
+/// setters can be chained.
+#[macro_export]
+macro_rules! builder {
+	(@build {$($struct:tt)+} {$($field:ident: $value:expr),* $(,)?}) => {
+		$crate::__internal_new!(@call {$($struct)+} builder {[] []} ())
+			$(.$field($value))*
+			.build()
+	};
+	(@build {$($struct:tt)+} {$($field:ident: $value:expr,)* ..}) => {
+		$crate::__internal_new!(@call {$($struct)+} builder {[] []} ())
+			$(.$field($value))*
+	};
+	($($input:tt)+) => {
+		$crate::__internal_braced!(builder!(@build) [] $($input)+)
+	};
+}
+
+/// A shortcut for [`FromStr::from_str`].
+///
+/// `parse!(Type: value)` parses anything that derefs to a `str` into `Type`,
+/// accepting the same types as [`try_from!`], generics included. `value` is
+/// borrowed, not moved.
+///
+/// [`FromStr::from_str`]: core::str::FromStr::from_str
+#[macro_export]
+macro_rules! parse {
+	($struct:ty: $value:expr) => {
+		<$struct as ::core::str::FromStr>::from_str(::core::convert::AsRef::<str>::as_ref(&$value))
+	};
+}
+
+/// A shortcut for [`Into::into`] with an explicit target type.
+///
+/// `into!(Type: value)` converts `value` into `Type`, accepting the same paths
+/// and generics as [`from!`]. Conversions can be chained through intermediate
+/// types with `into!(A => B => C: value)`, converting to `A` first and `C`
+/// last.
+#[macro_export]
+macro_rules! into {
+	(@chain $value:expr; $struct:ty) => {
+		::core::convert::Into::<$struct>::into($value)
+	};
+	(@chain $value:expr; $struct:ty => $($rest:tt)+) => {
+		$crate::into!(@chain ::core::convert::Into::<$struct>::into($value); $($rest)+)
+	};
+	($($struct:ty)=>+: $value:expr) => {
+		$crate::into!(@chain $value; $($struct)=>+)
+	};
+}
+
+/// A shortcut for [`TryInto::try_into`] with an explicit target type.
+///
+/// Mirrors [`into!`], including chains. A chain stops at the first failing
+/// step, converting its error into the error of the last step with [`From`].
+#[macro_export]
+macro_rules! try_into {
+	(@chain $value:expr; $struct:ty) => {
+		::core::convert::TryInto::<$struct>::try_into($value)
+	};
+	(@chain $value:expr; $struct:ty => $($rest:tt)+) => {
+		match ::core::convert::TryInto::<$struct>::try_into($value) {
+			::core::result::Result::Ok(value) => $crate::try_into!(@chain value; $($rest)+),
+			::core::result::Result::Err(err) => ::core::result::Result::Err(::core::convert::From::from(err)),
+		}
+	};
+	($($struct:ty)=>+: $value:expr) => {
+		$crate::try_into!(@chain $value; $($struct)=>+)
+	};
+}
+
+/// Builds a value that must be pinned before it is initialized.
+///
+/// `pin_new!(Type(args))` allocates an uninitialized, pinned slot and calls
+/// `Type::pinned_new(slot, args)`, returning a `Pin<Box<Type>>`. Like
+/// [`new!`], `pin_new!(Type: name(args))` calls `pinned_<name>` instead.
+/// Constructors have the signature
+/// `fn(Pin<&mut MaybeUninit<Self>>, args...) -> Pin<&mut Self>`, returning
+/// the slot once they have initialized it, and panicking if they return
+/// anything else.
+///
+/// `pin_new!(let name = form)` pins the slot on the stack instead, binding
+/// `name` to a `Pin<&mut Type>`. The value is dropped at the end of the
+/// enclosing scope.
+#[macro_export]
+macro_rules! pin_new {
+	(let mut $name:ident = $($input:tt)+) => {
+		let mut slot = $crate::__private::StackSlot::new();
+		// SAFETY: `slot` is hidden by hygiene, so it is never moved again.
+		let mut $name = unsafe { ::core::pin::Pin::new_unchecked(&mut slot) }
+			.init(|slot| $crate::__internal_new!([pinned_new] [pinned_] [] {[slot] []} $($input)+));
+	};
+	(let $name:ident = $($input:tt)+) => {
+		let mut slot = $crate::__private::StackSlot::new();
+		// SAFETY: `slot` is hidden by hygiene, so it is never moved again.
+		let $name = unsafe { ::core::pin::Pin::new_unchecked(&mut slot) }
+			.init(|slot| $crate::__internal_new!([pinned_new] [pinned_] [] {[slot] []} $($input)+));
+	};
+	($($input:tt)+) => {
+		$crate::__private::boxed(|slot| $crate::__internal_new!([pinned_new] [pinned_] [] {[slot] []} $($input)+))
+	};
+}
+
+/// Builds an [`Init`] that writes a value straight into its final location.
+///
+/// `init!(Type { field: value, other <- initializer })` initializes one field
+/// at a time: `field: value` moves `value` into place, while `other <- init`
+/// runs another [`Init`], like a nested `init!` or an [`init_array`], so
+/// large fields are never built on the stack. `field` alone is short for
+/// `field: field`. Every field must be listed, and `Type` accepts the same
+/// paths and generics as [`new!`].
+///
+/// If a field's value or initializer panics, the fields initialized before it
+/// are dropped.
+///
+/// Any other input is a [`new!`] form, whose value is moved into place.
+#[macro_export]
+macro_rules! init {
+	(@fields {$struct:ty} {$($fields:tt)*}) => {{
+		// Returning the proof means a field can't `return` out of the closure
+		// before the rest are initialized.
+		let init = |slot: *mut $struct| -> $crate::Initialized {
+			$crate::init!(@field slot {$struct} [] [] $($fields)*);
+
+			// SAFETY: Every field was initialized above.
+			unsafe { $crate::Initialized::new() }
+		};
+
+		// SAFETY: Every field is initialized before the closure returns, and the
+		// guards drop the initialized ones if a later field unwinds.
+		unsafe { $crate::Initializer::<$struct, _>::new(init) }
+	}};
+	(@field $slot:ident {$struct:ty} [$($names:tt)*] [$($guards:ident)*]) => {
+		// Never called, but checks that every field is listed, that none is
+		// reached through `Deref`, and that none is packed.
+		let _ = |value: &$struct| -> $struct {
+			$(let _ = &value.$names;)*
+			$crate::__private::Type::<$struct> { $($names: $crate::__private::unreachable()),* }
+		};
+		$(::core::mem::forget($guards);)*
+	};
+	(@field $slot:ident {$struct:ty} [$($names:tt)*] [$($guards:ident)*] $field:tt : $value:expr $(, $($rest:tt)*)?) => {
+		let value = $value;
+		// SAFETY: `slot` points to a `$struct`, and the check above makes sure
+		// `$field` is one of its own fields.
+		let field = unsafe { ::core::ptr::addr_of_mut!((*$slot).$field) };
+		// SAFETY: The field is valid for writes.
+		unsafe { field.write(value) };
+		// SAFETY: The field was just initialized.
+		let guard = unsafe { $crate::__private::DropGuard::new(field) };
+		$crate::init!(@field $slot {$struct} [$($names)* $field] [$($guards)* guard] $($($rest)*)?);
+	};
+	(@field $slot:ident {$struct:ty} [$($names:tt)*] [$($guards:ident)*] $field:tt <- $init:expr $(, $($rest:tt)*)?) => {
+		let init = $init;
+		// SAFETY: As above.
+		let field = unsafe { ::core::ptr::addr_of_mut!((*$slot).$field) };
+		// SAFETY: The field is valid for writes.
+		unsafe { $crate::Init::init(init, field) };
+		// SAFETY: The field was just initialized.
+		let guard = unsafe { $crate::__private::DropGuard::new(field) };
+		$crate::init!(@field $slot {$struct} [$($names)* $field] [$($guards)* guard] $($($rest)*)?);
+	};
+	(@field $slot:ident {$struct:ty} $names:tt $guards:tt $field:ident $(, $($rest:tt)*)?) => {
+		$crate::init!(@field $slot {$struct} $names $guards $field: $field $(, $($rest)*)?);
+	};
+	(@munch [$($struct:tt)+] {$($fields:tt)*}) => {
+		$crate::init!(@fields {$($struct)+} {$($fields)*})
+	};
+	(@munch [$($input:tt)+]) => {
+		$crate::__private::value($crate::new!($($input)+))
+	};
+	(@munch [$($input:tt)*] $next:tt $($rest:tt)*) => {
+		$crate::init!(@munch [$($input)* $next] $($rest)*)
+	};
+	($($input:tt)+) => {
+		$crate::init!(@munch [] $($input)+)
+	};
+}
+
+/// Initializes a [`Place`] with an [`init!`] form.
+///
+/// `emplace!(place => form)` is short for
+/// `Place::emplace(place, init!(form))`. Places are `Box<MaybeUninit<T>>`,
+/// `&mut MaybeUninit<T>`, and `&mut Vec<T>`, which pushes the value.
+#[macro_export]
+macro_rules! emplace {
+	($place:expr => $($input:tt)+) => {
+		$crate::Place::emplace($place, $crate::init!($($input)+))
+	};
+}
+
+/// A shortcut for [`Default::default`].
+///
+/// `default!(Type { field: value })` overrides the given fields with
+/// struct-update semantics, like `Type { field: value, ..Default::default() }`.
+/// The type accepts the same paths and generics as [`new!`].
+#[macro_export]
+macro_rules! default {
+	(@fields {$struct:ty} {$($field:tt $(: $value:expr)?),* $(,)?}) => {
+		$crate::__private::Type::<$struct> {
+			$($field $(: $value)?,)*
+			..<$struct as ::core::default::Default>::default()
+		}
+	};
+	($struct:ty) => {
+		<$struct as ::core::default::Default>::default()
+	};
+	($($input:tt)+) => {
+		$crate::__internal_braced!(default!(@fields) [] $($input)+)
+	};
+}
+
+/// A shortcut for [`TryDefault::try_default`].
+///
+/// Mirrors [`default!`], with `try_default!(Type { field: value })` only
+/// overriding fields when the default succeeds.
+#[macro_export]
+macro_rules! try_default {
+	(@fields {$struct:ty} {$($field:tt $(: $value:expr)?),* $(,)?}) => {
+		match <$struct as $crate::TryDefault>::try_default() {
+			// Unused when every field is overridden.
+			#[allow(unused_variables)]
+			::core::result::Result::Ok(default) => ::core::result::Result::Ok($crate::__private::Type::<$struct> {
+				$($field $(: $value)?,)*
+				..default
+			}),
+			::core::result::Result::Err(err) => ::core::result::Result::Err(err),
+		}
+	};
+	($struct:ty) => {
+		<$struct as $crate::TryDefault>::try_default()
+	};
+	($($input:tt)+) => {
+		$crate::__internal_braced!(try_default!(@fields) [] $($input)+)
+	};
+}
+
+#[cfg(all(test, feature = "std"))]
 mod tests {
-	use std::num::ParseIntError;
+	use allocator_api2::{
+		alloc::{AllocError, Allocator, Layout},
+		boxed::Box as ArenaBox,
+		vec::Vec as ArenaVec,
+	};
+
+	use super::{init_array, TryDefault};
+
+	use std::{
+		array::TryFromSliceError,
+		borrow::{Cow, ToOwned},
+		boxed::Box,
+		collections::HashMap,
+		future::Future,
+		marker::PhantomPinned,
+		mem::MaybeUninit,
+		num::{NonZero, ParseIntError, TryFromIntError},
+		pin::Pin,
+		rc::Rc,
+		string::String,
+		sync::{Arc, Mutex},
+		task::{Context, Poll, Waker},
+		vec,
+		vec::Vec,
+	};
 
 	#[derive(Debug, Default, PartialEq, Eq)]
 	struct Empty(Option<String>);
@@ -143,79 +889,994 @@ mod tests {
 		}
 	}
 
-	#[test]
-	fn empty_constructor_works() {
-		assert_eq!(new!(Empty()), Empty::default());
+	#[derive(Debug, PartialEq, Eq)]
+	struct Fixed<const N: usize>([u8; N]);
+
+	#[derive(Debug, PartialEq, Eq)]
+	struct Neg<const N: i8>;
+
+	impl<const N: i8> Neg<N> {
+		const fn new() -> Self {
+			Self
+		}
 	}
 
-	#[test]
-	fn constructor_with_args_works() {
-		let value = new!(ManyArgs(8u8, true, 7.0));
-		assert_eq!(
-			value,
-			ManyArgs {
-				value: 8,
-				thing: None,
-				other: true,
-				floating: 7.0
+	impl<const N: usize> Fixed<N> {
+		const fn new() -> Self {
+			Self([0; N])
+		}
+
+		const fn with_fill(fill: u8) -> Self {
+			Self([fill; N])
+		}
+
+		fn try_new(bytes: &[u8]) -> Result<Self, TryFromSliceError> {
+			Ok(Self(bytes.try_into()?))
+		}
+
+		const fn try_with_fill(fill: u8) -> Result<Self, u8> {
+			if fill == 0 {
+				Err(fill)
+			} else {
+				Ok(Self::with_fill(fill))
 			}
-		);
-	}
+		}
 
-	#[test]
-	fn other_constructor_works() {
-		let thing = String::from("Hello, world!");
-		assert_eq!(
-			new!(ManyArgs: with_thing(thing, 8u8, true, 7.0)),
-			ManyArgs {
-				thing: Some("Hello, world!".to_owned()),
-				value: 8,
-				other: true,
-				floating: 7.0,
+		const fn from_array(bytes: [u8; N]) -> Self {
+			Self(bytes)
+		}
+
+		const fn try_from_bytes(bytes: &[u8]) -> Result<Self, usize> {
+			match bytes.first_chunk() {
+				Some(array) if bytes.len() == N => Ok(Self(*array)),
+				_ => Err(bytes.len()),
 			}
-		);
+		}
 	}
 
-	#[test]
-	fn try_constructors_work() -> Result<(), ParseIntError> {
-		let try_new = try_new!(TryNew("7"))?;
+	#[derive(Debug, Default, PartialEq, Eq)]
+	struct Generic<T> {
+		value: T,
+		count: usize,
+	}
 
-		assert_eq!(try_new, TryNew(7));
+	#[derive(Debug, PartialEq, Eq)]
+	struct Pair(u32, u32);
 
-		let try_with_value = try_new!(TryOther: with_value("7"))?;
+	impl Pair {
+		fn new<T: Into<u32>>(value: T) -> Self {
+			let value = value.into();
 
-		assert_eq!(try_with_value, TryOther(Some(TryNew(7))));
+			Self(value, value)
+		}
 
-		Ok(())
+		fn from_parts<A: Into<u32>, B: Into<u32>>(left: A, right: B) -> Self {
+			Self(left.into(), right.into())
+		}
+
+		fn try_new<T: TryInto<u32>>(value: T) -> Result<Self, T::Error> {
+			value.try_into().map(Self::new)
+		}
 	}
 
-	#[test]
-	fn constructor_with_generics_works() {
-		assert_eq!(new!(Vec<u8>()), vec![]);
+	mod config {
+		#[derive(Debug, Default, PartialEq, Eq)]
+		pub struct Settings {
+			pub verbose: bool,
+		}
+
+		impl Settings {
+			pub const fn new() -> Self {
+				Self { verbose: false }
+			}
+
+			pub const fn with_verbose(verbose: bool) -> Self {
+				Self { verbose }
+			}
+		}
 	}
 
-	#[test]
-	fn with_constructor_works() {
-		let v = with!(Vec<u8>: capacity(7));
+	#[derive(Debug, PartialEq, Eq)]
+	struct Port(u16);
 
-		assert_eq!(v.capacity(), 7);
+	impl TryDefault for Port {
+		type Error = ParseIntError;
+
+		fn try_default() -> Result<Self, Self::Error> {
+			Ok(Self("8080".parse()?))
+		}
 	}
 
-	#[test]
-	fn convert_constructors_work() -> Result<(), ParseIntError> {
-		let b = Box::new(5);
+	/// Polls `future` to completion on the current thread.
+	fn block_on<F: Future>(future: F) -> F::Output {
+		let mut future = std::pin::pin!(future);
+		let mut cx = Context::from_waker(Waker::noop());
 
-		let ptr = Box::into_raw(b);
+		loop {
+			if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+				return output;
+			}
+		}
+	}
 
-		// SAFETY: It's the same pointer
-		let new_b = unsafe { from!(Box<u8>: raw(ptr)) };
+	/// Returns `Pending` once, so constructors actually suspend.
+	async fn yield_now() {
+		let mut yielded = false;
 
-		assert_eq!(*new_b, 5);
+		std::future::poll_fn(|cx| {
+			if yielded {
+				Poll::Ready(())
+			} else {
+				yielded = true;
+				cx.waker().wake_by_ref();
+				Poll::Pending
+			}
+		})
+		.await;
+	}
 
-		let value = try_from!(TryNew("7"))?;
+	#[derive(Debug, PartialEq, Eq)]
+	struct Connection {
+		port: u16,
+		secure: bool,
+	}
 
-		assert_eq!(value, TryNew(7));
+	impl Connection {
+		async fn new_async(port: u16) -> Self {
+			yield_now().await;
 
-		Ok(())
+			Self {
+				port,
+				secure: false,
+			}
+		}
+
+		async fn secure_async(port: u16) -> Self {
+			Self {
+				secure: true,
+				..Self::new_async(port).await
+			}
+		}
+
+		async fn try_new_async(port: &str) -> Result<Self, ParseIntError> {
+			Ok(Self::new_async(port.parse()?).await)
+		}
+
+		async fn try_secure_async(port: &str) -> Result<Self, ParseIntError> {
+			Ok(Self::secure_async(port.parse()?).await)
+		}
+
+		async fn with_tls_async(secure: bool, port: u16) -> Self {
+			Self {
+				secure,
+				..Self::new_async(port).await
+			}
+		}
+	}
+
+	impl super::New<()> for Empty {
+		fn new((): ()) -> Self {
+			Self::new()
+		}
+	}
+
+	impl<'a> super::TryNew<(&'a str,)> for TryNew {
+		type Error = ParseIntError;
+
+		fn try_new((value,): (&'a str,)) -> Result<Self, Self::Error> {
+			Self::try_new(value)
+		}
+	}
+
+	struct SelfRef {
+		value: u32,
+		ptr: *const u32,
+		guard: Option<Rc<()>>,
+		_pin: PhantomPinned,
+	}
+
+	impl SelfRef {
+		const fn pinned_new(slot: Pin<&mut MaybeUninit<Self>>, value: u32) -> Pin<&mut Self> {
+			// SAFETY: Nothing is moved out of the slot.
+			let slot = unsafe { slot.get_unchecked_mut() };
+			let this = slot.write(Self {
+				value,
+				ptr: std::ptr::null(),
+				guard: None,
+				_pin: PhantomPinned,
+			});
+			this.ptr = &raw const this.value;
+
+			// SAFETY: The value lives in the pinned slot.
+			unsafe { Pin::new_unchecked(this) }
+		}
+
+		fn pinned_guarded(
+			slot: Pin<&mut MaybeUninit<Self>>,
+			value: u32,
+			guard: Rc<()>,
+		) -> Pin<&mut Self> {
+			let mut this = Self::pinned_new(slot, value);
+			// SAFETY: Nothing is moved out of the value.
+			unsafe { this.as_mut().get_unchecked_mut() }.guard = Some(guard);
+			this
+		}
+
+		fn get(self: Pin<&Self>) -> u32 {
+			// SAFETY: `ptr` points into the pinned value itself.
+			unsafe { *self.ptr }
+		}
+	}
+
+	/// A bump arena over a fixed buffer that never frees.
+	struct Bump {
+		memory: std::cell::UnsafeCell<[MaybeUninit<u8>; 256]>,
+		used: std::cell::Cell<usize>,
+	}
+
+	impl Bump {
+		const fn new() -> Self {
+			Self {
+				memory: std::cell::UnsafeCell::new([MaybeUninit::uninit(); 256]),
+				used: std::cell::Cell::new(0),
+			}
+		}
+	}
+
+	// SAFETY: Allocations never overlap and stay valid as long as the arena.
+	unsafe impl Allocator for &Bump {
+		fn allocate(&self, layout: Layout) -> Result<std::ptr::NonNull<[u8]>, AllocError> {
+			let base = self.memory.get().cast::<u8>();
+			let start = self.used.get().next_multiple_of(layout.align());
+			let end = start
+				.checked_add(layout.size())
+				.filter(|&end| end <= 256)
+				.ok_or(AllocError)?;
+			self.used.set(end);
+
+			// SAFETY: `start..end` is in bounds of the buffer.
+			let ptr = unsafe { base.add(start) };
+			let slice = std::ptr::slice_from_raw_parts_mut(ptr, layout.size());
+			std::ptr::NonNull::new(slice).ok_or(AllocError)
+		}
+
+		unsafe fn deallocate(&self, _: std::ptr::NonNull<u8>, _: Layout) {}
+	}
+
+	#[derive(Debug, PartialEq, Eq)]
+	struct Buffer {
+		id: u32,
+		data: Vec<u8>,
+	}
+
+	impl Buffer {
+		const fn new(id: u32) -> Self {
+			Self {
+				id,
+				data: Vec::new(),
+			}
+		}
+
+		fn reinit(&mut self, id: u32) {
+			self.id = id;
+			self.data.clear();
+		}
+
+		fn with_fill(id: u32, fill: u8) -> Self {
+			Self {
+				id,
+				data: vec![fill; 4],
+			}
+		}
+
+		fn reinit_with_fill(&mut self, id: u32, fill: u8) {
+			self.reinit(id);
+			self.data.resize(4, fill);
+		}
+	}
+
+	#[derive(Debug, PartialEq, Eq)]
+	struct Handle {
+		path: &'static str,
+		write: bool,
+	}
+
+	impl Handle {
+		const fn open(path: &'static str) -> Self {
+			Self { path, write: false }
+		}
+
+		const fn open_write(path: &'static str) -> Self {
+			Self { path, write: true }
+		}
+
+		const fn try_open(path: &'static str) -> Result<Self, &'static str> {
+			if path.is_empty() {
+				Err("empty path")
+			} else {
+				Ok(Self::open(path))
+			}
+		}
+
+		const fn try_open_write(path: &'static str) -> Result<Self, &'static str> {
+			match Self::try_open(path) {
+				Ok(_) => Ok(Self::open_write(path)),
+				Err(error) => Err(error),
+			}
+		}
+	}
+
+	define_constructor_macro!(open, prefix = "open_", default = open, fallible = try_open);
+	define_constructor_macro!(lossy, suffix = "_lossy");
+
+	#[test]
+	fn empty_constructor_works() {
+		assert_eq!(new!(Empty()), Empty::default());
+	}
+
+	#[test]
+	fn constructor_with_args_works() {
+		let value = new!(ManyArgs(8u8, true, 7.0));
+		assert_eq!(
+			value,
+			ManyArgs {
+				value: 8,
+				thing: None,
+				other: true,
+				floating: 7.0
+			}
+		);
+	}
+
+	#[test]
+	fn other_constructor_works() {
+		let thing = String::from("Hello, world!");
+		assert_eq!(
+			new!(ManyArgs: with_thing(thing, 8u8, true, 7.0)),
+			ManyArgs {
+				thing: Some("Hello, world!".to_owned()),
+				value: 8,
+				other: true,
+				floating: 7.0,
+			}
+		);
+	}
+
+	#[test]
+	fn try_constructors_work() -> Result<(), ParseIntError> {
+		let try_new = try_new!(TryNew("7"))?;
+
+		assert_eq!(try_new, TryNew(7));
+
+		let try_with_value = try_new!(TryOther: with_value("7"))?;
+
+		assert_eq!(try_with_value, TryOther(Some(TryNew(7))));
+
+		Ok(())
+	}
+
+	#[test]
+	fn constructor_with_generics_works() {
+		assert_eq!(new!(Vec<u8>()), vec![]);
+	}
+
+	#[test]
+	fn with_constructor_works() {
+		let v = with!(Vec<u8>: capacity(7));
+
+		assert_eq!(v.capacity(), 7);
+	}
+
+	#[test]
+	#[rustfmt::skip]
+	fn constructor_generics_work() -> Result<(), TryFromIntError> {
+		assert_eq!(new!(Vec<u8>: from_iter::<[u8; 2]>([1, 2])), [1, 2]);
+		assert_eq!(new!(Vec::<u8>: from_iter::<Vec<_>>(vec![3])), [3]);
+		assert_eq!(new!(Pair: ::<u8>(7)), Pair(7, 7));
+		assert_eq!(new!(Pair: from_parts::<u8, _>(1, 2u16)), Pair(1, 2));
+		assert_eq!(try_new!(Pair: ::<u64>(300))?, Pair(300, 300));
+		assert!(try_new!(Pair: ::<u64>(u64::MAX)).is_err());
+		assert_eq!(from!(<Vec<u8> as FromIterator<u8>>: iter::<[u8; 1]>([4])), [4]);
+
+		Ok(())
+	}
+
+	#[test]
+	fn unchecked_constructors_work() {
+		// SAFETY: 7 is non-zero.
+		let value = unsafe { unchecked!(NonZero<u8>(7)) };
+		assert_eq!(value.get(), 7);
+
+		// SAFETY: The bytes are ASCII.
+		let text = unsafe { unchecked!(SAFETY: "the bytes are ASCII", str: from_utf8(b"ok")) };
+		assert_eq!(text, "ok");
+
+		// SAFETY: As above.
+		let owned = unsafe { unchecked!(String: from_utf8(vec![b'o', b'k'])) };
+		assert_eq!(owned, "ok");
+	}
+
+	#[test]
+	fn defined_constructor_macros_work() {
+		assert_eq!(open!(Handle("a")), Handle::open("a"));
+		assert_eq!(open!(self::Handle: write("a")), Handle::open_write("a"));
+		assert_eq!(try_open!(Handle("")), Err("empty path"));
+		assert_eq!(try_open!(Handle: write("a")), Ok(Handle::open_write("a")));
+
+		assert_eq!(lossy!(String: from_utf8(b"ok\xff")), "ok\u{fffd}");
+	}
+
+	#[test]
+	fn convert_constructors_work() -> Result<(), ParseIntError> {
+		let b = Box::new(5);
+
+		let ptr = Box::into_raw(b);
+
+		// SAFETY: It's the same pointer
+		let new_b = unsafe { from!(Box<u8>: raw(ptr)) };
+
+		assert_eq!(*new_b, 5);
+
+		let value = try_from!(TryNew("7"))?;
+
+		assert_eq!(value, TryNew(7));
+
+		Ok(())
+	}
+
+	#[test]
+	fn expression_arguments_work() -> Result<(), ParseIntError> {
+		let x = 7u8;
+		let flag = false;
+		let f = 49.0f32;
+
+		assert_eq!(
+			new!(ManyArgs(x + 1, !flag, f.sqrt())),
+			ManyArgs::new(8, true, 7.0)
+		);
+		assert_eq!(
+			new!(ManyArgs(
+				{
+					let y = x;
+					y + 1
+				},
+				matches!(x, 7),
+				f64::from(f).sqrt() as f32,
+			)),
+			ManyArgs::new(8, true, 7.0)
+		);
+		assert_eq!(
+			new!(ManyArgs: with_thing("Hello".to_owned(), x, flag, 1.0,)),
+			ManyArgs::with_thing("Hello".to_owned(), 7, false, 1.0)
+		);
+
+		let text = String::from("7");
+		assert_eq!(try_new!(TryNew(&text))?, TryNew(7));
+		assert_eq!(
+			try_new!(TryOther: with_value(text.as_str(),))?,
+			TryOther(Some(TryNew(7)))
+		);
+		assert_eq!(try_from!(TryNew(&*text,))?, TryNew(7));
+		assert_eq!(with!(Vec<u8>: capacity(usize::from(x) * 2)).capacity(), 14);
+		assert_eq!(from!(Option<u8>(f.sqrt() as u8)), Some(7));
+
+		let mapped = from!(Box<dyn Fn(u8) -> u8>(Box::new(|v: u8| v * 2) as Box<dyn Fn(u8) -> u8>));
+		assert_eq!(mapped(x), 14);
+
+		Ok(())
+	}
+
+	#[test]
+	#[rustfmt::skip]
+	fn multiple_generics_work() {
+		let map = new!(HashMap<String, u8>());
+		assert!(map.is_empty());
+
+		let map = with!(HashMap<String, u8>: capacity(4));
+		assert!(map.capacity() >= 4);
+	}
+
+	#[test]
+	fn nested_generics_work() {
+		assert_eq!(new!(Vec<Vec<u8>>()), Vec::<Vec<u8>>::new());
+		assert_eq!(new!(Vec<Option<Vec<u8>>>()), vec![]);
+		assert_eq!(
+			with!(Vec<HashMap<String, Vec<u8>>>: capacity(2)).capacity(),
+			2
+		);
+	}
+
+	#[test]
+	fn reference_generics_work() {
+		assert_eq!(new!(Vec<&str>()), Vec::<&str>::new());
+		assert_eq!(new!(Vec<&'static mut [u8]>()).len(), 0);
+		assert_eq!(from!(Option<&str>("a")), Some("a"));
+	}
+
+	#[test]
+	fn trait_object_generics_work() {
+		let callback: Box<dyn Fn() -> u8> = Box::new(|| 7);
+		let callback = from!(Box<dyn Fn() -> u8>(callback));
+		assert_eq!(callback(), 7);
+
+		let list = new!(Vec<Box<dyn Fn(u8) -> u8 + Send>>());
+		assert!(list.is_empty());
+	}
+
+	#[test]
+	fn lifetime_generics_work() {
+		let cow = from!(Cow<'static, str>("a"));
+		assert_eq!(cow, "a");
+
+		let list = with!(Vec<Cow<'_, [u8]>>: capacity(1));
+		assert_eq!(list.capacity(), 1);
+	}
+
+	#[test]
+	fn const_generics_work() {
+		assert_eq!(new!(Fixed<4>()), Fixed([0; 4]));
+		assert_eq!(new!(Fixed<{ 2 + 2 }>()), Fixed([0; 4]));
+		assert_eq!(new!(Neg<-1>()), Neg);
+		assert_eq!(with!(Fixed<3>: fill(7)), Fixed([7; 3]));
+		assert_eq!(try_new!(Fixed<2>: with_fill(1)), Ok(Fixed([1; 2])));
+		assert_eq!(try_new!(Fixed<2>: with_fill(0)), Err(0));
+	}
+
+	#[test]
+	#[rustfmt::skip]
+	fn inferred_generics_work() {
+		let mut list = new!(Vec<_>());
+		list.extend([7u8]);
+		assert_eq!(list, [7]);
+
+		let map: HashMap<u8, bool> = new!(HashMap<_, _>());
+		assert!(map.is_empty());
+	}
+
+	#[test]
+	fn shorthand_generics_work() -> Result<(), TryFromIntError> {
+		assert_eq!(try_from!(NonZero<u32>(7))?, NonZero::new(7).unwrap());
+		assert!(try_from!(NonZero<u32>(0)).is_err());
+		let wide = NonZero::new(300u32).unwrap();
+		assert!(try_from!(NonZero<u8>(wide)).is_err());
+
+		let s = String::from("boxed");
+		assert_eq!(&*from!(Box<str>(s)), "boxed");
+		assert_eq!(from!(Vec<u8>("abc")), b"abc");
+		assert_eq!(from!(std::sync::Arc<[u8]>(vec![1, 2])).len(), 2);
+
+		assert_eq!(try_new!(Fixed<2>(&[1, 2])).ok(), Some(Fixed([1, 2])));
+		assert!(try_new!(Fixed<2>(&[1, 2, 3])).is_err());
+
+		Ok(())
+	}
+
+	#[test]
+	#[rustfmt::skip]
+	fn paths_work() -> Result<(), ParseIntError> {
+		assert_eq!(
+			new!(std::collections::HashMap<String, u8>()),
+			std::collections::HashMap::new()
+		);
+		assert_eq!(new!(::std::vec::Vec<u8>()), vec![]);
+		assert_eq!(new!(crate::tests::config::Settings()), config::Settings::default());
+		assert_eq!(new!(self::Empty()), Empty::default());
+		assert_eq!(
+			with!(super::tests::config::Settings: verbose(true)),
+			config::Settings { verbose: true }
+		);
+		assert_eq!(try_new!(self::TryNew("7"))?, TryNew(7));
+		assert_eq!(try_from!(crate::tests::TryNew("7"))?, TryNew(7));
+		assert_eq!(from!(std::string::String("a")), "a");
+
+		Ok(())
+	}
+
+	#[test]
+	fn qualified_paths_work() {
+		assert_eq!(new!(<Empty as Default>: default()), Empty::default());
+		assert_eq!(new!(<Vec<u8>>()), vec![]);
+		assert_eq!(
+			new!(<config::Settings as Default>: default()),
+			config::Settings::default()
+		);
+	}
+
+	#[test]
+	fn default_works() {
+		assert_eq!(default!(Vec<u8>), Vec::<u8>::new());
+		assert_eq!(default!(Empty), Empty::default());
+		assert_eq!(
+			default!(crate::tests::config::Settings),
+			config::Settings::default()
+		);
+		assert_eq!(default!(<Vec<u8> as IntoIterator>::IntoIter).len(), 0);
+	}
+
+	#[test]
+	fn default_with_fields_works() {
+		let floating = 7.0;
+		assert_eq!(
+			default!(ManyArgs { value: 8, floating }),
+			ManyArgs::new(8, false, 7.0)
+		);
+		assert_eq!(
+			default!(Generic<u8> { value: 7 }),
+			Generic { value: 7, count: 0 }
+		);
+		let inferred: Generic<String> = default!(Generic<_> { count: 1 });
+		assert_eq!(inferred.value, "");
+	}
+
+	#[test]
+	fn try_default_works() -> Result<(), ParseIntError> {
+		assert_eq!(try_default!(Port)?, Port(8080));
+		assert_eq!(try_default!(Vec<u8>), Ok(vec![]));
+		assert_eq!(
+			try_default!(Generic<u8> { value: 7 }),
+			Ok(Generic { value: 7, count: 0 })
+		);
+
+		Ok(())
+	}
+
+	#[test]
+	fn into_works() {
+		assert_eq!(into!(u64: 7u32), 7);
+		assert_eq!(into!(std::string::String: "a"), "a");
+		assert_eq!(&*into!(Box<str>: String::from("boxed")), "boxed");
+		assert_eq!(into!(Option<Vec<u8>>: vec![1]), Some(vec![1]));
+	}
+
+	#[test]
+	fn into_chains_work() {
+		assert_eq!(into!(u16 => u32 => u64: 7u8), 7u64);
+		assert_eq!(
+			into!(String => Box<str> => Rc<str>: "chained"),
+			Rc::from("chained")
+		);
+	}
+
+	#[test]
+	fn try_into_works() {
+		assert_eq!(try_into!(u8: 7u32), Ok(7));
+		assert!(try_into!(u8: 300u32).is_err());
+		assert_eq!(try_into!(NonZero<u8>: 7u8).map(NonZero::get), Ok(7));
+	}
+
+	#[test]
+	fn try_into_chains_work() {
+		assert_eq!(try_into!(u16 => u8: 7u32), Ok(7));
+		assert!(try_into!(u16 => u8: 70_000u32).is_err());
+		assert!(try_into!(u16 => u8: 300u32).is_err());
+		assert!(try_into!(u32 => u16: -1i64).is_err());
+	}
+
+	#[test]
+	fn parse_works() {
+		let owned = String::from("-3");
+
+		assert_eq!(parse!(u8: "7"), Ok(7));
+		assert!(parse!(u8: "300").is_err());
+		assert_eq!(parse!(i64: owned), Ok(-3));
+		assert_eq!(owned, "-3");
+		assert_eq!(
+			parse!(std::net::Ipv4Addr: "127.0.0.1"),
+			Ok(std::net::Ipv4Addr::LOCALHOST)
+		);
+		assert_eq!(parse!(NonZero<u16>: "9").map(NonZero::get), Ok(9));
+	}
+
+	#[test]
+	fn const_contexts_work() {
+		const EMPTY: Empty = new!(Empty());
+		static MANY: ManyArgs = new!(ManyArgs(8, true, 7.0));
+		const SETTINGS: config::Settings = new!(self::config::Settings: with_verbose(true));
+		const QUALIFIED: Fixed<2> = new!(<Fixed<2>>());
+		const FILLED: Fixed<2> = with!(Fixed<2>: fill(1));
+		const TRIED: Result<Fixed<2>, u8> = try_new!(Fixed<2>: with_fill(0));
+		static FROM: Fixed<2> = from!(Fixed<2>: array([1, 2]));
+		static TRY_FROM: Result<Fixed<2>, usize> = try_from!(Fixed<2>: bytes(&[1, 2, 3]));
+
+		assert_eq!(EMPTY, Empty(None));
+		assert_eq!(MANY, ManyArgs::new(8, true, 7.0));
+		assert_eq!(SETTINGS, config::Settings::with_verbose(true));
+		assert_eq!(QUALIFIED, Fixed([0; 2]));
+		assert_eq!(FILLED, Fixed([1; 2]));
+		assert_eq!(TRIED, Err(0));
+		assert_eq!(FROM, Fixed([1, 2]));
+		assert_eq!(TRY_FROM, Err(3));
+	}
+
+	#[test]
+	fn const_new_works() {
+		const fn fixed<const N: usize>() -> Fixed<N> {
+			const_new!(Fixed<N>: with_fill(7))
+		}
+
+		assert_eq!(const_new!(Empty()), Empty(None));
+		assert_eq!(fixed::<3>(), Fixed([7; 3]));
+	}
+
+	#[test]
+	fn async_constructors_work() {
+		block_on(async {
+			assert_eq!(new_async!(Connection(80)), Connection::new_async(80).await);
+			assert!(new_async!(self::Connection: secure(443)).secure);
+			assert_eq!(
+				with_async!(Connection: tls(true, 8443)),
+				Connection::secure_async(8443).await
+			);
+		});
+	}
+
+	#[test]
+	fn try_async_constructors_work() -> Result<(), ParseIntError> {
+		block_on(async {
+			assert_eq!(try_new_async!(Connection("80"))?.port, 80);
+			assert!(try_new_async!(Connection: secure("443"))?.secure);
+			assert!(try_new_async!(Connection("http")).is_err());
+
+			Ok(())
+		})
+	}
+
+	#[test]
+	fn trait_constructors_work() {
+		fn make<T: super::New<()>>() -> T {
+			new!(T as New())
+		}
+
+		assert_eq!(make::<Empty>(), Empty(None));
+		assert_eq!(new!(self::Empty as New()), Empty(None));
+		assert_eq!(try_new!(TryNew as TryNew("7")), Ok(TryNew(7)));
+		assert!(try_new!(TryNew as TryNew("seven",)).is_err());
+	}
+
+	#[test]
+	fn wrapper_forms_work() {
+		let shared = new!(Arc<Mutex<_>> <- ManyArgs(8, true, 7.0));
+		shared.lock().unwrap().value = 9;
+		assert_eq!(shared.lock().unwrap().value, 9);
+
+		let boxed: Box<Fixed<4>> = new!(std::boxed::Box<_> <- Fixed<4>: with_fill(1));
+		assert_eq!(*boxed, Fixed([1; 4]));
+
+		let pinned: Pin<Box<Empty>> = new!(Pin<Box<_>> <- Empty());
+		assert_eq!(*pinned, Empty(None));
+	}
+
+	#[test]
+	fn wrapper_forms_take_macros() -> Result<(), ParseIntError> {
+		assert_eq!(new!(Box<_> <- try_new!(TryNew("7"))?), Box::new(TryNew(7)));
+		assert_eq!(new!(Rc<_> <- with!(Vec<u8>: capacity(7))).capacity(), 7);
+
+		Ok(())
+	}
+
+	#[test]
+	fn wrapper_macros_work() {
+		assert_eq!(boxed!(Empty()), Box::new(Empty(None)));
+		assert_eq!(
+			rc!(ManyArgs(8, true, 7.0)),
+			Rc::new(ManyArgs::new(8, true, 7.0))
+		);
+		assert_eq!(
+			*arc!(config::Settings: with_verbose(true)),
+			config::Settings::with_verbose(true)
+		);
+		assert_eq!(*pin!(vec![1, 2]), [1, 2]);
+	}
+
+	struct Big {
+		len: usize,
+		data: [u8; 4 << 20],
+	}
+
+	#[test]
+	fn wrapper_forms_initialize_in_place() {
+		// Larger than a test thread's stack.
+		let boxed = new!(Box<_> <- init!(Big {
+			len: 1,
+			data <- init_array(|i| i as u8),
+		}));
+		assert_eq!(boxed.data[258], 2);
+
+		let shared = arc!(init!(Big {
+			len: 2,
+			data <- init_array(|_| 3),
+		}));
+		assert_eq!(shared.len, 2);
+		assert_eq!(shared.data[7], 3);
+
+		let counted = rc!(crate::init!(Big {
+			len: 3,
+			data <- init_array(|_| 4),
+		}));
+		assert_eq!(counted.data[7], 4);
+
+		let cell = new!(std::cell::RefCell<_> <- init!(ManyArgs(8, true, 7.0)));
+		assert_eq!(cell.borrow().value, 8);
+	}
+
+	#[test]
+	fn emplace_works() {
+		// Larger than a test thread's stack.
+		let big = emplace!(Box::new_uninit() => Big {
+			len: 7,
+			data <- init_array(|i| i as u8),
+		});
+		assert_eq!(big.len, 7);
+		assert_eq!(big.data[257], 1);
+
+		let mut slot = MaybeUninit::uninit();
+		let many = emplace!(&mut slot => ManyArgs(8, true, 7.0));
+		many.value = 9;
+		assert_eq!(many.value, 9);
+
+		let mut generics = Vec::new();
+		let value = 3;
+		emplace!(&mut generics => Generic<u8> { value, count: 1 });
+		emplace!(&mut generics => Generic::<u8> { count: 2, value: 4 });
+		assert_eq!(
+			generics,
+			[
+				Generic { value: 3, count: 1 },
+				Generic { value: 4, count: 2 }
+			]
+		);
+	}
+
+	#[test]
+	fn nested_init_works() {
+		let boxed = emplace!(Box::new_uninit() => Fixed<2> {
+			0 <- init_array(|_| 1),
+		});
+		assert_eq!(*boxed, Fixed([1; 2]));
+
+		let nested = emplace!(Box::new_uninit() => Generic<Generic<u8>> {
+			value <- init!(Generic<u8> { value: 1, count: 2 }),
+			count: 3,
+		});
+		assert_eq!(nested.value, Generic { value: 1, count: 2 });
+	}
+
+	#[test]
+	fn init_drops_fields_on_panic() {
+		fn fail<T>() -> T {
+			panic!("field failed")
+		}
+
+		let shared = Rc::new(());
+
+		let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+			emplace!(Box::new_uninit() => Generic<[Rc<()>; 4]> {
+				value <- init_array(|i| {
+					assert!(i < 2, "element {i}");
+					Rc::clone(&shared)
+				}),
+				count: 0,
+			})
+		}));
+		assert!(result.is_err());
+		assert_eq!(Rc::strong_count(&shared), 1);
+
+		let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+			emplace!(Box::new_uninit() => Generic<Rc<()>> {
+				value: Rc::clone(&shared),
+				count: fail(),
+			})
+		}));
+		assert!(result.is_err());
+		assert_eq!(Rc::strong_count(&shared), 1);
+	}
+
+	#[test]
+	fn pinned_constructors_work() {
+		let boxed = pin_new!(SelfRef(7));
+		assert_eq!(boxed.as_ref().get(), 7);
+
+		pin_new!(let stack = self::SelfRef(8));
+		assert_eq!(stack.as_ref().get(), 8);
+	}
+
+	#[test]
+	fn stack_pins_drop_their_value() {
+		let shared = Rc::new(());
+
+		{
+			pin_new!(let mut stack = SelfRef: guarded(9, Rc::clone(&shared)));
+			assert_eq!(stack.as_mut().as_ref().get(), 9);
+			assert_eq!(Rc::strong_count(&shared), 2);
+		}
+		assert_eq!(Rc::strong_count(&shared), 1);
+
+		drop(pin_new!(SelfRef: guarded(9, Rc::clone(&shared))));
+		assert_eq!(Rc::strong_count(&shared), 1);
+	}
+
+	#[test]
+	#[should_panic = "pinned constructors must return the slot they were given"]
+	fn pinned_constructors_must_return_their_slot() {
+		struct Elsewhere {
+			_byte: u8,
+		}
+
+		impl Elsewhere {
+			fn pinned_new(_: Pin<&mut MaybeUninit<Self>>) -> Pin<&mut Self> {
+				Pin::new(Box::leak(Box::new(Self { _byte: 0 })))
+			}
+		}
+
+		drop(pin_new!(Elsewhere()));
+	}
+
+	#[test]
+	fn allocator_constructors_work() {
+		let arena = Bump::new();
+
+		let boxed = new_in!(ArenaBox<u32, _>(7) in &arena);
+		assert_eq!(*boxed, 7);
+
+		let mut vec = new_in!(ArenaVec<u8, _>: with_capacity(8) in &arena);
+		vec.extend([1, 2, 3]);
+		assert_eq!(vec, [1, 2, 3]);
+		assert_eq!(
+			new_in!(allocator_api2::vec::Vec::<u8, _>() in &arena).capacity(),
+			0
+		);
+		assert!(arena.used.get() >= 12);
+	}
+
+	#[test]
+	fn try_allocator_constructors_work() {
+		let arena = Bump::new();
+
+		assert_eq!(
+			try_new_in!(ArenaBox<u64, _>(7) in &arena).as_deref(),
+			Ok(&7)
+		);
+		assert!(try_new_in!(ArenaBox<[u8; 512], _>([0; 512]) in &arena).is_err());
+	}
+
+	#[test]
+	fn pools_recycle_values() {
+		let pool = super::Pool::new();
+
+		{
+			let mut first = new!(in pool => Buffer(1));
+			first.data.extend([1; 64]);
+			let second = new!(in pool => Buffer: with_fill(2, 7));
+			assert_eq!(*second, Buffer::with_fill(2, 7));
+		}
+		assert_eq!(pool.available(), 2);
+
+		// The last slot returned is reused first.
+		let reused = new!(in pool => Buffer(3));
+		assert_eq!(*reused, Buffer::new(3));
+		assert!(reused.data.capacity() >= 64);
+		let reused = new!(in pool => self::Buffer: with_fill(4, 9));
+		assert_eq!(*reused, Buffer::with_fill(4, 9));
+
+		assert_eq!(
+			pool.stats(),
+			super::PoolStats {
+				hits: 2,
+				misses: 2,
+				live: 2,
+				high_water: 2,
+			}
+		);
+	}
+
+	#[test]
+	fn pooled_values_can_be_detached() {
+		let pool = super::Pool::new();
+
+		let detached = super::Pooled::into_inner(new!(in pool => Buffer(1)));
+		assert_eq!(detached, Buffer::new(1));
+		assert_eq!(pool.available(), 0);
+		assert_eq!(pool.stats().live, 0);
+		assert_eq!(pool.stats().high_water, 1);
 	}
 }
@@ -0,0 +1,18 @@
+/// Constructors that can be called with named arguments.
+///
+/// `#[derive(New)]` implements this with a generated `<Struct>NewArgs`
+/// struct holding one public field per constructor parameter, which is what
+/// lets `new!(Type { name: value })` reorder its arguments at compile time.
+pub trait NamedNew: Sized {
+	/// The named arguments of the constructor.
+	type Args;
+
+	/// Calls the constructor with the given named arguments.
+	fn new_named(args: Self::Args) -> Self;
+}
+
+/// The named arguments of `T`'s constructor.
+///
+/// Unlike `<T as NamedNew>::Args`, this alias can be used in struct
+/// expressions.
+pub type NamedArgs<T> = <T as NamedNew>::Args;
@@ -0,0 +1,29 @@
+use core::{error::Error, fmt};
+
+/// The error returned when parsing an enum from `#[derive(FromStr)]` fails.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseVariantError {
+	type_name: &'static str,
+}
+
+impl ParseVariantError {
+	/// Creates an error for the enum named `type_name`.
+	#[must_use]
+	pub const fn new(type_name: &'static str) -> Self {
+		Self { type_name }
+	}
+
+	/// The name of the enum that failed to parse.
+	#[must_use]
+	pub const fn type_name(&self) -> &'static str {
+		self.type_name
+	}
+}
+
+impl fmt::Display for ParseVariantError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "unknown `{}` variant", self.type_name)
+	}
+}
+
+impl Error for ParseVariantError {}
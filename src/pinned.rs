@@ -0,0 +1,87 @@
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+use core::{
+	marker::PhantomPinned,
+	mem::MaybeUninit,
+	pin::Pin,
+	ptr::{self, addr_of_mut},
+};
+
+/// Checks that a pinned constructor initialized the slot it was given.
+fn check<T>(slot: *const MaybeUninit<T>, value: &T) {
+	assert!(
+		ptr::eq(slot.cast::<T>(), value),
+		"pinned constructors must return the slot they were given"
+	);
+}
+
+/// Runs a pinned constructor on a new heap slot, used by [`pin_new!`].
+///
+/// [`pin_new!`]: crate::pin_new
+#[cfg(feature = "alloc")]
+#[doc(hidden)]
+pub fn boxed<T, F>(init: F) -> Pin<Box<T>>
+where
+	F: for<'a> FnOnce(Pin<&'a mut MaybeUninit<T>>) -> Pin<&'a mut T>,
+{
+	let mut slot = Box::into_pin(Box::<T>::new_uninit());
+	let expected = ptr::from_ref::<MaybeUninit<T>>(&slot);
+	let value = init(slot.as_mut());
+	check(expected, &value);
+
+	// SAFETY: The constructor initialized the slot, and the box is pinned
+	// again right away.
+	unsafe { Box::into_pin(Pin::into_inner_unchecked(slot).assume_init()) }
+}
+
+/// A pinned slot on the stack that drops its value once initialized, used by
+/// `pin_new!(let ..)`.
+#[doc(hidden)]
+pub struct StackSlot<T> {
+	value: MaybeUninit<T>,
+	init: bool,
+	_pin: PhantomPinned,
+}
+
+impl<T> StackSlot<T> {
+	#[doc(hidden)]
+	#[must_use]
+	pub const fn new() -> Self {
+		Self {
+			value: MaybeUninit::uninit(),
+			init: false,
+			_pin: PhantomPinned,
+		}
+	}
+
+	/// Runs a pinned constructor on the slot.
+	#[doc(hidden)]
+	pub fn init<'a, F>(self: Pin<&'a mut Self>, init: F) -> Pin<&'a mut T>
+	where
+		F: FnOnce(Pin<&'a mut MaybeUninit<T>>) -> Pin<&'a mut T>,
+	{
+		// SAFETY: Nothing is moved out of the slot.
+		let this: *mut Self = unsafe { self.get_unchecked_mut() };
+		// SAFETY: `this` comes from a live `&mut`, and the value is pinned
+		// along with the slot.
+		let slot = unsafe { Pin::new_unchecked(&mut *addr_of_mut!((*this).value)) };
+		let value = init(slot);
+		// SAFETY: `init` doesn't overlap with the borrowed value.
+		unsafe {
+			check(addr_of_mut!((*this).value), &value);
+			(*this).init = true;
+		}
+
+		value
+	}
+}
+
+impl<T> Drop for StackSlot<T> {
+	fn drop(&mut self) {
+		if self.init {
+			// SAFETY: The value was initialized, and pinned values must be
+			// dropped before their memory is reused.
+			unsafe { self.value.assume_init_drop() };
+		}
+	}
+}
@@ -0,0 +1,158 @@
+use alloc::{boxed::Box, vec::Vec};
+use core::{
+	cell::{Cell, RefCell},
+	fmt,
+	mem::ManuallyDrop,
+	ops::{Deref, DerefMut},
+};
+
+/// Counters describing how a [`Pool`] has been used, for sizing it.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct PoolStats {
+	/// Values built by reinitializing a recycled slot.
+	pub hits: usize,
+	/// Values built from scratch because no slot was free.
+	pub misses: usize,
+	/// Values currently checked out.
+	pub live: usize,
+	/// The most values ever checked out at once.
+	pub high_water: usize,
+}
+
+/// A pool of boxed values that are recycled instead of freed, used by
+/// `new!(in pool => Type(args))`.
+///
+/// A recycled slot is reinitialized in place with `Type::reinit(&mut value,
+/// args)`, or `Type::reinit_<name>` for `Type: name(args)`, and a new value
+/// is built with the matching [`new!`] form when no slot is free. Values are
+/// handed out as [`Pooled`] guards that return their slot on drop, keeping
+/// its allocations for the next `reinit`.
+///
+/// [`new!`]: crate::new
+pub struct Pool<T> {
+	free: RefCell<Vec<Box<T>>>,
+	stats: Cell<PoolStats>,
+}
+
+impl<T> Pool<T> {
+	/// Creates an empty pool.
+	#[must_use]
+	pub const fn new() -> Self {
+		Self {
+			free: RefCell::new(Vec::new()),
+			stats: Cell::new(PoolStats {
+				hits: 0,
+				misses: 0,
+				live: 0,
+				high_water: 0,
+			}),
+		}
+	}
+
+	/// Returns the pool's usage counters.
+	pub const fn stats(&self) -> PoolStats {
+		self.stats.get()
+	}
+
+	/// Returns the number of free slots.
+	pub fn available(&self) -> usize {
+		self.free.borrow().len()
+	}
+
+	/// Takes a free slot, counting a hit, or counts a miss.
+	#[doc(hidden)]
+	pub fn recycle(&self) -> Option<Box<T>> {
+		let slot = self.free.borrow_mut().pop();
+		self.update(|stats| {
+			if slot.is_some() {
+				stats.hits += 1;
+			} else {
+				stats.misses += 1;
+			}
+		});
+
+		slot
+	}
+
+	/// Hands out an initialized slot.
+	#[doc(hidden)]
+	pub fn check_out(&self, value: Box<T>) -> Pooled<'_, T> {
+		self.update(|stats| {
+			stats.live += 1;
+			stats.high_water = stats.high_water.max(stats.live);
+		});
+
+		Pooled {
+			value: ManuallyDrop::new(value),
+			pool: self,
+		}
+	}
+
+	fn update(&self, update: impl FnOnce(&mut PoolStats)) {
+		let mut stats = self.stats.get();
+		update(&mut stats);
+		self.stats.set(stats);
+	}
+}
+
+impl<T> Default for Pool<T> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<T> fmt::Debug for Pool<T> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("Pool")
+			.field("available", &self.available())
+			.field("stats", &self.stats())
+			.finish()
+	}
+}
+
+/// A value checked out of a [`Pool`], returned to it on drop.
+pub struct Pooled<'a, T> {
+	value: ManuallyDrop<Box<T>>,
+	pool: &'a Pool<T>,
+}
+
+impl<T> Pooled<'_, T> {
+	/// Detaches the value from its pool, which no longer counts it as live.
+	#[must_use]
+	pub fn into_inner(this: Self) -> T {
+		let mut this = ManuallyDrop::new(this);
+		this.pool.update(|stats| stats.live -= 1);
+
+		// SAFETY: `this` is never dropped, so the value is taken only once.
+		*unsafe { ManuallyDrop::take(&mut this.value) }
+	}
+}
+
+impl<T> Deref for Pooled<'_, T> {
+	type Target = T;
+
+	fn deref(&self) -> &Self::Target {
+		&self.value
+	}
+}
+
+impl<T> DerefMut for Pooled<'_, T> {
+	fn deref_mut(&mut self) -> &mut Self::Target {
+		&mut self.value
+	}
+}
+
+impl<T: fmt::Debug> fmt::Debug for Pooled<'_, T> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		fmt::Debug::fmt(&**self, f)
+	}
+}
+
+impl<T> Drop for Pooled<'_, T> {
+	fn drop(&mut self) {
+		// SAFETY: The value is taken only here, and `self` isn't used again.
+		let value = unsafe { ManuallyDrop::take(&mut self.value) };
+		self.pool.free.borrow_mut().push(value);
+		self.pool.update(|stats| stats.live -= 1);
+	}
+}
@@ -0,0 +1,28 @@
+/// Types with a `new` constructor taking `Args`, a tuple of its parameters.
+///
+/// This lets generic code require a constructor, as in
+/// `T: New<(u8, bool, f32)>`, and call it with
+/// `new!(T as New(8, true, 7.0))`. `#[derive(New)]` implements it with the
+/// parameter types of the generated constructor.
+pub trait New<Args>: Sized {
+	/// Calls the constructor with the given arguments.
+	fn new(args: Args) -> Self;
+}
+
+/// Types with a fallible `try_new` constructor taking `Args`, a tuple of its
+/// parameters.
+///
+/// The fallible counterpart of [`New`], called with
+/// `try_new!(T as TryNew(args))`. `#[derive(TryNew)]` implements it with the
+/// generated error type.
+pub trait TryNew<Args>: Sized {
+	/// The error returned when construction fails.
+	type Error;
+
+	/// Calls the constructor with the given arguments.
+	///
+	/// # Errors
+	///
+	/// Returns an error when the arguments are rejected.
+	fn try_new(args: Args) -> Result<Self, Self::Error>;
+}
@@ -0,0 +1,124 @@
+#[cfg(all(feature = "alloc", target_has_atomic = "ptr"))]
+use alloc::sync::Arc;
+#[cfg(feature = "alloc")]
+use alloc::{boxed::Box, rc::Rc};
+#[cfg(feature = "alloc")]
+use core::pin::Pin;
+use core::{
+	cell::{Cell, RefCell},
+	mem::MaybeUninit,
+};
+#[cfg(feature = "std")]
+use std::sync::{Mutex, RwLock};
+
+use crate::Init;
+
+/// One layer of wrapping around a value, used by `new!(Wrapper<_> <- ...)`.
+///
+/// Each implementation wraps exactly one layer, so `Arc<Mutex<_>>` is built
+/// as a `Mutex` wrapped in an `Arc`. Values from `new!` forms are fully built
+/// before they are wrapped, so they pass through the stack. `init!` forms go
+/// through [`wrap_init`](Wrap::wrap_init) instead, which `Box`, `Rc` and
+/// `Arc` implement by initializing the value straight into a new allocation.
+pub trait Wrap<T>: Sized {
+	/// Wraps `value`.
+	fn wrap(value: T) -> Self;
+
+	/// Wraps the value `init` initializes.
+	///
+	/// By default the value is initialized on the stack and passed to
+	/// [`wrap`](Wrap::wrap).
+	#[inline]
+	fn wrap_init<I: Init<T>>(init: I) -> Self {
+		let mut slot = MaybeUninit::uninit();
+
+		// SAFETY: The slot is valid for writes, and `init` leaves it holding a
+		// valid `T` when it returns.
+		unsafe {
+			init.init(slot.as_mut_ptr());
+			Self::wrap(slot.assume_init())
+		}
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Wrap<T> for Box<T> {
+	#[inline]
+	fn wrap(value: T) -> Self {
+		Self::new(value)
+	}
+
+	#[inline]
+	fn wrap_init<I: Init<T>>(init: I) -> Self {
+		let mut boxed = Self::new_uninit();
+
+		// SAFETY: As for the default, with a slot on the heap.
+		unsafe {
+			init.init(boxed.as_mut_ptr());
+			boxed.assume_init()
+		}
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Wrap<T> for Rc<T> {
+	#[inline]
+	fn wrap(value: T) -> Self {
+		Self::new(value)
+	}
+
+	#[inline]
+	fn wrap_init<I: Init<T>>(init: I) -> Self {
+		let mut rc = Self::new_uninit();
+		let slot = Rc::get_mut(&mut rc).expect("a new `Rc` is unique");
+
+		// SAFETY: As for `Box`.
+		unsafe {
+			init.init(slot.as_mut_ptr());
+			rc.assume_init()
+		}
+	}
+}
+
+#[cfg(all(feature = "alloc", target_has_atomic = "ptr"))]
+impl<T> Wrap<T> for Arc<T> {
+	#[inline]
+	fn wrap(value: T) -> Self {
+		Self::new(value)
+	}
+
+	#[inline]
+	fn wrap_init<I: Init<T>>(init: I) -> Self {
+		let mut arc = Self::new_uninit();
+		let slot = Arc::get_mut(&mut arc).expect("a new `Arc` is unique");
+
+		// SAFETY: As for `Box`.
+		unsafe {
+			init.init(slot.as_mut_ptr());
+			arc.assume_init()
+		}
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Wrap<Box<T>> for Pin<Box<T>> {
+	#[inline]
+	fn wrap(value: Box<T>) -> Self {
+		Box::into_pin(value)
+	}
+}
+
+macro_rules! cells {
+	($($cell:ident),*) => {$(
+		impl<T> Wrap<T> for $cell<T> {
+			#[inline]
+			fn wrap(value: T) -> Self {
+				Self::new(value)
+			}
+		}
+	)*};
+}
+
+cells!(Cell, RefCell);
+#[cfg(feature = "std")]
+cells!(Mutex, RwLock);
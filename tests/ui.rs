@@ -0,0 +1,6 @@
+//! Checks that misuse of the macros fails to compile with useful errors.
+
+#[test]
+fn ui() {
+	trybuild::TestCases::new().compile_fail("tests/ui/*.rs");
+}
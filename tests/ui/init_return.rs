@@ -0,0 +1,13 @@
+use new::emplace;
+
+struct Pair {
+	first: String,
+	second: String,
+}
+
+fn main() {
+	let _ = emplace!(Box::new_uninit() => Pair {
+		first: String::from("x"),
+		second: return,
+	});
+}
@@ -0,0 +1,8 @@
+use std::num::NonZero;
+
+use new::unchecked;
+
+fn main() {
+	// SAFETY: Never runs, since the argument doesn't type-check.
+	let _ = unsafe { unchecked!(SAFETY: "seven is non-zero", NonZero<u8>("seven")) };
+}
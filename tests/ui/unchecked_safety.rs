@@ -0,0 +1,7 @@
+use std::num::NonZero;
+
+use new::unchecked;
+
+fn main() {
+	let _ = unchecked!(SAFETY: "seven is non-zero", NonZero<u8>(7));
+}